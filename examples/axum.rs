@@ -0,0 +1,122 @@
+//! A small `axum` app selling a product with Polar: a checkout route, a webhook route and an access check.
+//!
+//! Customers are only known by the ID of the user in your app, Polar keeps it as their external ID. Storing users,
+//! e.g. in a database, and reporting usage are left out.
+//!
+//! It runs against the sandbox, with the following environment variables:
+//!
+//! - `POLAR_ACCESS_TOKEN`: an organization access token of the sandbox.
+//! - `POLAR_WEBHOOK_SECRET`: the secret of a webhook endpoint pointing to `/webhooks`, e.g. through a tunnel.
+//!
+//! ```bash
+//! cargo run --example axum
+//! ```
+//!
+//! Then open `http://localhost:3000/checkout/usr_1337` to buy the product as the user `usr_1337` of your app, and
+//! `http://localhost:3000/access/usr_1337` to check they have access to it.
+
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use polar_rs::prelude::*;
+use polar_rs::types::Uuid;
+use polar_rs::{Order, RecurringInterval, WebhookEventType, quickstart};
+use serde_json::json;
+
+struct AppState {
+    polar: Polar,
+    webhook_secret: String,
+    product_id: Uuid,
+}
+
+struct AppError(PolarError);
+
+impl From<PolarError> for AppError {
+    fn from(err: PolarError) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = match self.0 {
+            PolarError::NotFound => StatusCode::NOT_FOUND,
+            PolarError::Unauthorized => StatusCode::FORBIDDEN,
+            PolarError::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => StatusCode::BAD_GATEWAY,
+        };
+
+        (status, self.0.to_string()).into_response()
+    }
+}
+
+async fn checkout(State(state): State<Arc<AppState>>, Path(user_id): Path<String>) -> Result<Redirect, AppError> {
+    let checkout =
+        quickstart::create_checkout_for(&state.polar, state.product_id, &user_id, "http://localhost:3000/").await?;
+
+    Ok(Redirect::to(checkout.url.as_str()))
+}
+
+async fn webhooks(State(state): State<Arc<AppState>>, headers: HeaderMap, body: Bytes) -> Result<StatusCode, AppError> {
+    let event = quickstart::handle_webhook(&state.webhook_secret, &headers, &body)?;
+
+    match event.r#type {
+        WebhookEventType::OrderPaid => {
+            let order: Order = event.data_as()?;
+
+            println!("Order {} paid by customer {}", order.id, order.customer_id);
+        }
+        WebhookEventType::SubscriptionRevoked => {
+            let subscription: Subscription = event.data_as()?;
+
+            println!("Subscription {} revoked", subscription.id);
+        }
+        _ => {}
+    }
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+async fn access(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<String>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    // Users who never went through a checkout are unknown to Polar.
+    let has_access = match state.polar.get_customer_by_external_id(&user_id).await {
+        Ok(customer) => quickstart::customer_has_access(&state.polar, customer.id, state.product_id).await?,
+        Err(PolarError::NotFound) => false,
+        Err(err) => return Err(err.into()),
+    };
+
+    Ok(Json(json!({ "has_access": has_access })))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let polar = Polar::new("https://sandbox-api.polar.sh/v1/", std::env::var("POLAR_ACCESS_TOKEN")?)?;
+    let product = quickstart::ensure_product(&polar, "Pro plan", 1000, "usd", Some(RecurringInterval::Month)).await?;
+    let state = Arc::new(AppState {
+        polar,
+        webhook_secret: std::env::var("POLAR_WEBHOOK_SECRET")?,
+        product_id: product.id,
+    });
+
+    let app = Router::new()
+        .route("/checkout/{user_id}", get(checkout))
+        .route("/webhooks", post(webhooks))
+        .route("/access/{user_id}", get(access))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
+
+    println!("Listening on http://{}", listener.local_addr()?);
+
+    axum::serve(listener, app).await?;
+
+    Ok(())
+}
@@ -0,0 +1,409 @@
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::enums::*;
+use crate::models::*;
+use crate::{Polar, PolarResult};
+
+/// An amount paid by a customer, e.g. an order.
+pub trait CustomerRevenue {
+    /// The ID of the paying customer.
+    fn customer_id(&self) -> Uuid;
+    /// Currency code of the amount.
+    fn currency(&self) -> &str;
+    /// Amount in cents.
+    fn amount(&self) -> u64;
+}
+
+impl CustomerRevenue for Order {
+    fn customer_id(&self) -> Uuid {
+        self.customer_id
+    }
+
+    fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    /// Net amount of the order, minus refunds, zero if fully refunded.
+    fn amount(&self) -> u64 {
+        u64::try_from(self.net_amount.saturating_sub(self.refunded_amount)).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CancellationReasonSummary {
+    /// Reason given by the customers, `None` when they didn't give any.
+    pub reason: Option<CustomerCancellationReason>,
+    /// Currency code of the amounts.
+    pub currency: String,
+    /// Number of canceled subscriptions.
+    pub cancellations: usize,
+    /// Monthly recurring revenue of the canceled subscriptions in cents.
+    pub mrr: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CustomerLifetimeValue {
+    /// The ID of the customer.
+    pub customer_id: Uuid,
+    /// Currency code of the amount.
+    pub currency: String,
+    /// Total amount paid by the customer in cents.
+    pub amount: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RecurringRevenue {
+    /// Currency code of the amounts.
+    pub currency: String,
+    /// Monthly recurring revenue in cents.
+    pub mrr: u64,
+    /// Annual recurring revenue in cents.
+    pub arr: u64,
+    /// Number of subscriptions contributing to the revenue.
+    pub subscriptions: usize,
+}
+
+/// Computes the monthly and annual recurring revenue of active subscriptions, grouped by currency.
+///
+/// Amounts of non-monthly subscriptions are prorated over an average year (365.25 days, 365.25 / 7 weeks) before being
+/// split into months, so the result is rounded down only once per currency.
+pub fn recurring_revenue<'a, I>(subscriptions: I) -> Vec<RecurringRevenue>
+where
+    I: IntoIterator<Item = &'a Subscription>,
+{
+    let mut amounts: BTreeMap<&str, (u64, usize)> = BTreeMap::new();
+
+    for subscription in subscriptions {
+        if !matches!(subscription.status, SubscriptionStatus::Active) {
+            continue;
+        }
+
+        let entry = amounts.entry(&subscription.currency).or_default();
+
+        entry.0 = entry.0.saturating_add(amount_over_years(subscription));
+        entry.1 += 1;
+    }
+
+    amounts
+        .into_iter()
+        .map(|(currency, (amount, subscriptions))| RecurringRevenue {
+            currency: currency.to_owned(),
+            mrr: amount / (YEARS * 12),
+            arr: amount / YEARS,
+            subscriptions,
+        })
+        .collect()
+}
+
+/// Groups the subscriptions canceled during a period by cancellation reason and currency.
+///
+/// A subscription counts as canceled when its `canceled_at` is within the period, even if it's still active until the
+/// end of its billing period. Results are sorted by reason, then currency.
+pub fn cancellations_by_reason<'a, I>(subscriptions: I, period: &Range<DateTime<Utc>>) -> Vec<CancellationReasonSummary>
+where
+    I: IntoIterator<Item = &'a Subscription>,
+{
+    let mut amounts: BTreeMap<(Option<CustomerCancellationReason>, &str), (u64, usize)> = BTreeMap::new();
+
+    for subscription in subscriptions {
+        if !subscription
+            .canceled_at
+            .is_some_and(|canceled_at| period.contains(&canceled_at))
+        {
+            continue;
+        }
+
+        let entry = amounts
+            .entry((subscription.customer_cancellation_reason, &subscription.currency))
+            .or_default();
+
+        entry.0 = entry.0.saturating_add(amount_over_years(subscription));
+        entry.1 += 1;
+    }
+
+    amounts
+        .into_iter()
+        .map(
+            |((reason, currency), (amount, cancellations))| CancellationReasonSummary {
+                reason,
+                currency: currency.to_owned(),
+                cancellations,
+                mrr: amount / (YEARS * 12),
+            },
+        )
+        .collect()
+}
+
+/// Fetches every subscription matching the params, page by page, and groups the ones canceled during the period with
+/// [`cancellations_by_reason`].
+///
+/// `page` is ignored, pages are requested with the given `limit` or the maximum of 100.
+pub async fn fetch_cancellations_by_reason(
+    polar: &Polar,
+    mut params: ListSubscriptionsParams,
+    period: &Range<DateTime<Utc>>,
+) -> PolarResult<Vec<CancellationReasonSummary>> {
+    params.page = Some(1);
+    params.limit = params.limit.or(Some(100));
+
+    let subscriptions = polar.list_subscriptions(&params).await?.collect_items(polar).await?;
+
+    Ok(cancellations_by_reason(&subscriptions, period))
+}
+
+/// Computes the total amount paid by each customer, grouped by currency.
+///
+/// Results are sorted by customer ID and currency.
+pub fn lifetime_values<'a, I, R>(revenues: I) -> Vec<CustomerLifetimeValue>
+where
+    I: IntoIterator<Item = &'a R>,
+    R: CustomerRevenue + 'a,
+{
+    let mut amounts: BTreeMap<(Uuid, String), u64> = BTreeMap::new();
+
+    for revenue in revenues {
+        let amount = amounts
+            .entry((revenue.customer_id(), revenue.currency().to_owned()))
+            .or_default();
+
+        *amount = amount.saturating_add(revenue.amount());
+    }
+
+    amounts
+        .into_iter()
+        .map(|((customer_id, currency), amount)| CustomerLifetimeValue {
+            customer_id,
+            currency,
+            amount,
+        })
+        .collect()
+}
+
+/// Number of years amounts are summed over before being divided, the smallest one holding a whole number of days and
+/// weeks with 365.25 days per year.
+const YEARS: u64 = 28;
+
+/// Amount of a subscription over [`YEARS`] years, in cents.
+fn amount_over_years(subscription: &Subscription) -> u64 {
+    let periods = match subscription.recurring_interval {
+        RecurringInterval::Day => 1461 * 7,
+        RecurringInterval::Week => 1461,
+        RecurringInterval::Month => 12 * YEARS,
+        RecurringInterval::Year => YEARS,
+    };
+
+    u64::try_from(subscription.amount).unwrap_or(0).saturating_mul(periods)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    use super::*;
+    use crate::test_support::get_fixture;
+
+    struct TestRevenue(Uuid, &'static str, u64);
+
+    impl CustomerRevenue for TestRevenue {
+        fn customer_id(&self) -> Uuid {
+            self.0
+        }
+
+        fn currency(&self) -> &str {
+            self.1
+        }
+
+        fn amount(&self) -> u64 {
+            self.2
+        }
+    }
+
+    fn get_subscription(
+        status: SubscriptionStatus,
+        amount: i64,
+        currency: &str,
+        recurring_interval: RecurringInterval,
+    ) -> Subscription {
+        let mut subscription: Subscription = get_fixture("subscription");
+
+        subscription.status = status;
+        subscription.amount = amount;
+        subscription.currency = currency.to_owned();
+        subscription.recurring_interval = recurring_interval;
+
+        subscription
+    }
+
+    #[test]
+    fn should_compute_recurring_revenue_by_currency() {
+        let subscriptions = [
+            get_subscription(SubscriptionStatus::Active, 1000, "usd", RecurringInterval::Month),
+            get_subscription(SubscriptionStatus::Active, 12000, "usd", RecurringInterval::Year),
+            get_subscription(SubscriptionStatus::Active, 100, "usd", RecurringInterval::Week),
+            get_subscription(SubscriptionStatus::Active, 500, "eur", RecurringInterval::Month),
+            get_subscription(SubscriptionStatus::Canceled, 9999, "usd", RecurringInterval::Month),
+        ];
+
+        let result = recurring_revenue(&subscriptions);
+
+        assert_eq!(
+            result,
+            vec![
+                RecurringRevenue {
+                    currency: "eur".to_owned(),
+                    mrr: 500,
+                    arr: 6000,
+                    subscriptions: 1,
+                },
+                RecurringRevenue {
+                    currency: "usd".to_owned(),
+                    mrr: 2434,
+                    arr: 29217,
+                    subscriptions: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_compute_lifetime_values_by_customer_and_currency() {
+        let customer_id = Uuid::new_v4();
+        let revenues = [
+            TestRevenue(customer_id, "usd", 1000),
+            TestRevenue(customer_id, "usd", 500),
+            TestRevenue(customer_id, "eur", 700),
+        ];
+
+        let result = lifetime_values(&revenues);
+
+        assert_eq!(
+            result,
+            vec![
+                CustomerLifetimeValue {
+                    customer_id,
+                    currency: "eur".to_owned(),
+                    amount: 700,
+                },
+                CustomerLifetimeValue {
+                    customer_id,
+                    currency: "usd".to_owned(),
+                    amount: 1500,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_saturate_lifetime_values() {
+        let customer_id = Uuid::new_v4();
+        let revenues = [
+            TestRevenue(customer_id, "usd", u64::MAX),
+            TestRevenue(customer_id, "usd", 1000),
+        ];
+
+        let result = lifetime_values(&revenues);
+
+        assert_eq!(result[0].amount, u64::MAX);
+    }
+
+    #[test]
+    fn should_not_count_refunds_above_order_amount() {
+        let mut order: Order = get_fixture("order");
+
+        order.net_amount = 1000;
+        order.refunded_amount = 1500;
+
+        assert_eq!(order.amount(), 0);
+    }
+
+    fn get_canceled_subscription(
+        reason: Option<CustomerCancellationReason>,
+        amount: i64,
+        canceled_at: &str,
+    ) -> Subscription {
+        let mut subscription = get_subscription(SubscriptionStatus::Canceled, amount, "usd", RecurringInterval::Month);
+
+        subscription.customer_cancellation_reason = reason;
+        subscription.canceled_at = Some(canceled_at.parse().unwrap());
+
+        subscription
+    }
+
+    #[test]
+    fn should_group_cancellations_by_reason() {
+        let period = "2025-01-01T00:00:00Z".parse().unwrap().."2025-02-01T00:00:00Z".parse().unwrap();
+        let subscriptions = [
+            get_canceled_subscription(
+                Some(CustomerCancellationReason::TooExpensive),
+                1000,
+                "2025-01-05T00:00:00Z",
+            ),
+            get_canceled_subscription(
+                Some(CustomerCancellationReason::TooExpensive),
+                500,
+                "2025-01-31T23:59:59Z",
+            ),
+            get_canceled_subscription(Some(CustomerCancellationReason::Unused), 700, "2025-01-10T00:00:00Z"),
+            get_canceled_subscription(None, 300, "2025-01-20T00:00:00Z"),
+            get_canceled_subscription(Some(CustomerCancellationReason::Unused), 9999, "2025-02-01T00:00:00Z"),
+            get_canceled_subscription(Some(CustomerCancellationReason::Unused), 9999, "2024-12-31T23:59:59Z"),
+        ];
+
+        let result = cancellations_by_reason(&subscriptions, &period);
+
+        assert_eq!(
+            result,
+            vec![
+                CancellationReasonSummary {
+                    reason: None,
+                    currency: "usd".to_owned(),
+                    cancellations: 1,
+                    mrr: 300,
+                },
+                CancellationReasonSummary {
+                    reason: Some(CustomerCancellationReason::TooExpensive),
+                    currency: "usd".to_owned(),
+                    cancellations: 2,
+                    mrr: 1500,
+                },
+                CancellationReasonSummary {
+                    reason: Some(CustomerCancellationReason::Unused),
+                    currency: "usd".to_owned(),
+                    cancellations: 1,
+                    mrr: 700,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_fetch_cancellations_by_reason_from_every_page() {
+        let mock = MockServer::start().await;
+        let subscription: Value = get_fixture("subscription");
+
+        for page in ["1", "2"] {
+            Mock::given(matchers::method("GET"))
+                .and(matchers::path("/subscriptions"))
+                .and(matchers::query_param("page", page))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "items": [subscription],
+                    "pagination": { "total_count": 2, "max_page": 2 },
+                })))
+                .expect(1)
+                .mount(&mock)
+                .await;
+        }
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+        let period = "2023-11-01T00:00:00Z".parse().unwrap().."2023-12-01T00:00:00Z".parse().unwrap();
+
+        let result = fetch_cancellations_by_reason(&polar, ListSubscriptionsParams::default(), &period).await;
+
+        assert!(result.is_ok_and(|summaries| summaries[0].cancellations == 2));
+    }
+}
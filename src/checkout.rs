@@ -0,0 +1,308 @@
+use reqwest::IntoUrl;
+use serde::Serialize;
+use url::Url;
+use uuid::Uuid;
+
+use crate::{AttachedCustomField, BillingAddressField, BillingAddressFields, CustomFieldType, PolarError, PolarResult};
+
+/// Placeholder replaced by Polar with the ID of the checkout session when redirecting to `success_url`.
+pub const CHECKOUT_ID_PLACEHOLDER: &str = "{CHECKOUT_ID}";
+
+const CHECKOUT_ID_PARAM: &str = "checkout_id";
+
+/// Builds a `success_url` for a checkout session, adding the given query params and a `checkout_id` param set to
+/// [`CHECKOUT_ID_PLACEHOLDER`].
+///
+/// The params are percent-encoded, but the placeholder is kept as is, otherwise Polar wouldn't be able to replace it.
+pub fn build_success_url<U, I, K, V>(base_url: U, params: I) -> PolarResult<Url>
+where
+    U: IntoUrl,
+    I: IntoIterator<Item = (K, V)>,
+    K: AsRef<str>,
+    V: AsRef<str>,
+{
+    let mut url = base_url
+        .into_url()
+        .map_err(|_| PolarError::Request("base_url is not a valid URL".to_owned()))?;
+
+    url.query_pairs_mut()
+        .extend_pairs(params)
+        .append_pair(CHECKOUT_ID_PARAM, "");
+
+    let query = format!("{}{CHECKOUT_ID_PLACEHOLDER}", url.query().unwrap_or_default());
+
+    url.set_query(Some(&query));
+
+    Ok(url)
+}
+
+/// Extracts the `checkout_id` query param from the URL a customer was redirected to after a successful checkout.
+///
+/// Returns `None` if the param is missing or isn't a valid ID.
+pub fn parse_checkout_id(url: &Url) -> Option<Uuid> {
+    url.query_pairs()
+        .find(|(key, _)| key == CHECKOUT_ID_PARAM)
+        .and_then(|(_, value)| value.parse().ok())
+}
+
+/// A field of a checkout form, independent of any template engine or frontend framework.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FormField {
+    /// Name of the field, matching the checkout update params, e.g. `customer_billing_address.country` or
+    /// `custom_field_data.<slug>`.
+    pub name: String,
+    /// Label displayed to the customer.
+    pub label: String,
+    pub kind: FormFieldKind,
+    /// Whether a value must be provided.
+    pub required: bool,
+    pub help_text: Option<String>,
+    pub placeholder: Option<String>,
+    /// Minimum length of text fields.
+    pub min_length: Option<usize>,
+    /// Maximum length of text fields.
+    pub max_length: Option<usize>,
+    /// Minimum value of number fields.
+    pub min: Option<usize>,
+    /// Maximum value of number fields.
+    pub max: Option<usize>,
+    /// Options of select fields, empty for other kinds.
+    pub options: Vec<FormFieldOption>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FormFieldKind {
+    Checkbox,
+    /// A two-letter country code, e.g. `US`.
+    Country,
+    Date,
+    Number,
+    Select,
+    Text,
+    Textarea,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct FormFieldOption {
+    pub value: String,
+    pub label: String,
+}
+
+impl FormField {
+    fn new(name: String, label: String, kind: FormFieldKind, required: bool) -> Self {
+        Self {
+            name,
+            label,
+            kind,
+            required,
+            help_text: None,
+            placeholder: None,
+            min_length: None,
+            max_length: None,
+            min: None,
+            max: None,
+            options: Vec::new(),
+        }
+    }
+}
+
+/// Builds the fields of a checkout form from the billing address fields and the custom fields of a checkout session,
+/// so custom checkout forms can be rendered without hand-coding them per product.
+///
+/// Disabled billing address fields are left out, custom fields follow the billing address in their attached order.
+pub fn checkout_form_schema(
+    billing_address_fields: &BillingAddressFields,
+    custom_fields: &[AttachedCustomField],
+) -> Vec<FormField> {
+    let billing_address = [
+        ("country", "Country", &billing_address_fields.country),
+        ("line1", "Address line 1", &billing_address_fields.line1),
+        ("line2", "Address line 2", &billing_address_fields.line2),
+        ("postal_code", "Postal code", &billing_address_fields.postal_code),
+        ("city", "City", &billing_address_fields.city),
+        ("state", "State", &billing_address_fields.state),
+    ];
+
+    let mut fields = billing_address
+        .into_iter()
+        .filter(|(_, _, mode)| !matches!(mode, BillingAddressField::Disabled))
+        .map(|(name, label, mode)| {
+            let kind = if name == "country" {
+                FormFieldKind::Country
+            } else {
+                FormFieldKind::Text
+            };
+
+            FormField::new(
+                format!("customer_billing_address.{name}"),
+                label.to_owned(),
+                kind,
+                matches!(mode, BillingAddressField::Required),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut custom_fields = custom_fields.iter().collect::<Vec<_>>();
+
+    custom_fields.sort_by_key(|attached| attached.order);
+
+    fields.extend(custom_fields.into_iter().map(|attached| {
+        let custom_field = &attached.custom_field;
+        let properties = &custom_field.properties;
+        let kind = match custom_field.r#type {
+            CustomFieldType::Checkbox => FormFieldKind::Checkbox,
+            CustomFieldType::Date => FormFieldKind::Date,
+            CustomFieldType::Number => FormFieldKind::Number,
+            CustomFieldType::Select => FormFieldKind::Select,
+            CustomFieldType::Text if properties.textarea == Some(true) => FormFieldKind::Textarea,
+            CustomFieldType::Text => FormFieldKind::Text,
+        };
+
+        FormField {
+            help_text: Some(properties.form_help_text.clone()),
+            placeholder: Some(properties.form_placeholder.clone()),
+            min_length: properties.min_length,
+            max_length: properties.max_length,
+            min: properties.ge,
+            max: properties.le,
+            options: properties
+                .options
+                .iter()
+                .flatten()
+                .map(|option| FormFieldOption {
+                    value: option.value.clone(),
+                    label: option.label.clone(),
+                })
+                .collect(),
+            ..FormField::new(
+                format!("custom_field_data.{}", custom_field.slug),
+                properties.form_label.clone(),
+                kind,
+                attached.required,
+            )
+        }
+    }));
+
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{from_value, json};
+
+    use super::*;
+
+    fn get_custom_field(slug: &str, r#type: &str, order: usize, properties: serde_json::Value) -> AttachedCustomField {
+        let mut field_properties = json!({
+            "form_label": slug.to_uppercase(),
+            "form_help_text": "<string>",
+            "form_placeholder": "<string>",
+        });
+
+        field_properties
+            .as_object_mut()
+            .unwrap()
+            .extend(properties.as_object().unwrap().clone());
+
+        from_value(json!({
+            "custom_field_id": Uuid::new_v4(),
+            "custom_field": {
+                "created_at": "2023-11-07T05:31:56Z",
+                "modified_at": null,
+                "id": Uuid::new_v4(),
+                "metadata": {},
+                "type": r#type,
+                "slug": slug,
+                "name": slug,
+                "organization_id": Uuid::new_v4(),
+                "properties": field_properties,
+            },
+            "order": order,
+            "required": order == 0,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn should_build_success_url() {
+        let result = build_success_url("https://example.com/success?plan=pro", [("next", "/a b&c")]);
+
+        assert_eq!(
+            result.unwrap().as_str(),
+            "https://example.com/success?plan=pro&next=%2Fa+b%26c&checkout_id={CHECKOUT_ID}"
+        );
+    }
+
+    #[test]
+    fn should_not_build_success_url_when_base_url_is_invalid() {
+        let result = build_success_url("/success", [("next", "/")]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_parse_checkout_id() {
+        let checkout_id = Uuid::new_v4();
+        let url = Url::parse(&format!(
+            "https://example.com/success?plan=pro&checkout_id={checkout_id}"
+        ))
+        .unwrap();
+
+        assert_eq!(parse_checkout_id(&url), Some(checkout_id));
+    }
+
+    #[test]
+    fn should_not_parse_checkout_id_when_placeholder_was_not_replaced() {
+        let url = build_success_url("https://example.com/success", [("plan", "pro")]).unwrap();
+
+        assert_eq!(parse_checkout_id(&url), None);
+    }
+
+    #[test]
+    fn should_build_checkout_form_schema() {
+        let billing_address_fields: BillingAddressFields = from_value(json!({
+            "country": "required",
+            "state": "disabled",
+            "city": "optional",
+            "postal_code": "optional",
+            "line1": "disabled",
+            "line2": "disabled",
+        }))
+        .unwrap();
+        let custom_fields = [
+            get_custom_field(
+                "plan",
+                "select",
+                1,
+                json!({ "options": [{ "value": "pro", "label": "Pro" }] }),
+            ),
+            get_custom_field("company", "text", 0, json!({ "textarea": true, "max_length": 100 })),
+        ];
+
+        let fields = checkout_form_schema(&billing_address_fields, &custom_fields);
+        let summary = fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.kind, field.required))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            summary,
+            [
+                ("customer_billing_address.country", FormFieldKind::Country, true),
+                ("customer_billing_address.postal_code", FormFieldKind::Text, false),
+                ("customer_billing_address.city", FormFieldKind::Text, false),
+                ("custom_field_data.company", FormFieldKind::Textarea, true),
+                ("custom_field_data.plan", FormFieldKind::Select, false),
+            ]
+        );
+        assert_eq!(fields[3].max_length, Some(100));
+        assert_eq!(
+            fields[4].options,
+            [FormFieldOption {
+                value: "pro".to_owned(),
+                label: "Pro".to_owned(),
+            }]
+        );
+    }
+}
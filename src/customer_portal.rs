@@ -0,0 +1,171 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::Write;
+
+use chrono::Utc;
+use reqwest::StatusCode;
+use reqwest::header::RANGE;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    CustomerPortalCustomer, Downloadable, FileDownloadUrl, ListDownloadablesParams, ListResponse, Polar, PolarError,
+    PolarResult,
+};
+
+/// Number of times an interrupted download is resumed before giving up.
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_DOWNLOAD_RESUMES: usize = 3;
+
+/// Client for the customer portal API, acting on behalf of a single customer.
+///
+/// It's created with a customer access token with [`Polar::with_customer_token`], or from an organization client with
+/// [`Polar::customer_portal_from_session_token`].
+///
+/// ```no_run
+/// use polar_rs::Polar;
+///
+/// # async fn run(customer_token: &str) -> polar_rs::PolarResult<()> {
+/// let customer_portal = Polar::with_customer_token("https://sandbox-api.polar.sh/v1/", customer_token)?;
+/// let customer = customer_portal.get_customer().await?;
+///
+/// println!("Signed in as {}", customer.email);
+/// # Ok(())
+/// # }
+/// ```
+pub struct CustomerPortal {
+    polar: Polar,
+}
+
+impl CustomerPortal {
+    pub(crate) fn new(polar: Polar) -> Self {
+        Self { polar }
+    }
+
+    /// **Get authenticated customer.**
+    ///
+    /// Scopes: `customer_portal:read` `customer_portal:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/customer-portal/get-customer>
+    pub async fn get_customer(&self) -> PolarResult<CustomerPortalCustomer> {
+        self.polar.get("customer-portal/customers/me").await
+    }
+
+    /// **List downloadables.**
+    ///
+    /// Scopes: `customer_portal:read` `customer_portal:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/customer-portal/downloadables/list>
+    pub async fn list_downloadables(
+        &self,
+        params: &ListDownloadablesParams,
+    ) -> PolarResult<ListResponse<Downloadable>> {
+        self.polar.get_list("customer-portal/downloadables", params).await
+    }
+
+    /// Fetches the page after `list` with the customer token, or returns `None` if `list` is the last page. Same as
+    /// [`ListResponse::next_page`], for lists returned by the customer portal.
+    pub async fn next_page<T: DeserializeOwned>(&self, list: &ListResponse<T>) -> PolarResult<Option<ListResponse<T>>> {
+        list.next_page(&self.polar).await
+    }
+
+    /// Downloads the file of a downloadable.
+    ///
+    /// Download URLs are short-lived: when the URL is expired, or the storage answers with `403 Forbidden` or
+    /// `410 Gone`, a fresh one is fetched from the downloadables of the same benefit and the download is retried once.
+    ///
+    /// The response is returned as is so the file can be streamed.
+    pub async fn download(&self, downloadable: &Downloadable) -> PolarResult<reqwest::Response> {
+        self.download_from(downloadable, 0).await
+    }
+
+    /// Downloads the file of a downloadable into `writer`, returning the number of bytes written.
+    ///
+    /// When the connection drops mid-way, the download is resumed from the last received byte with a `Range` request,
+    /// up to 3 times. If the storage ignores the range and sends the whole file again, the part already written is
+    /// skipped. Not available on WebAssembly, use [`CustomerPortal::download`] there.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn download_into<W: Write>(&self, downloadable: &Downloadable, writer: &mut W) -> PolarResult<u64> {
+        let mut response = self.download(downloadable).await?;
+        let mut written = 0;
+        let mut to_skip = 0;
+        let mut resumes = 0;
+
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    let skipped = to_skip.min(chunk.len() as u64);
+                    let chunk = &chunk[skipped as usize..];
+
+                    to_skip -= skipped;
+                    writer.write_all(chunk)?;
+                    written += chunk.len() as u64;
+                }
+                Ok(None) => {
+                    writer.flush()?;
+
+                    return Ok(written);
+                }
+                Err(_) if resumes < MAX_DOWNLOAD_RESUMES => {
+                    resumes += 1;
+                    response = self.download_from(downloadable, written).await?;
+                    to_skip = if response.status() == StatusCode::PARTIAL_CONTENT {
+                        0
+                    } else {
+                        written
+                    };
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+
+    async fn download_from(&self, downloadable: &Downloadable, offset: u64) -> PolarResult<reqwest::Response> {
+        if downloadable.file.download.expires_at > Utc::now() {
+            let response = self.fetch(&downloadable.file.download, offset).await?;
+
+            if !matches!(response.status(), StatusCode::FORBIDDEN | StatusCode::GONE) {
+                return Self::ensure_success(response).await;
+            }
+        }
+
+        let downloadable = self.refresh_downloadable(downloadable).await?;
+
+        Self::ensure_success(self.fetch(&downloadable.file.download, offset).await?).await
+    }
+
+    async fn fetch(&self, download: &FileDownloadUrl, offset: u64) -> PolarResult<reqwest::Response> {
+        // The URL is signed, the access token must not be sent to the storage.
+        let mut request = self.polar.client.get(download.url.clone());
+
+        for (name, value) in &download.headers {
+            request = request.header(name, value);
+        }
+
+        if offset > 0 {
+            request = request.header(RANGE, format!("bytes={offset}-"));
+        }
+
+        Ok(request.send().await?)
+    }
+
+    async fn refresh_downloadable(&self, downloadable: &Downloadable) -> PolarResult<Downloadable> {
+        let params = ListDownloadablesParams {
+            benefit_id: Some(vec![downloadable.benefit_id]),
+            page: None,
+            limit: Some(100),
+        };
+
+        self.list_downloadables(&params)
+            .await?
+            .find_item(&self.polar, |item| item.id == downloadable.id)
+            .await?
+            .ok_or(PolarError::NotFound)
+    }
+
+    async fn ensure_success(response: reqwest::Response) -> PolarResult<reqwest::Response> {
+        match response.status() {
+            status if status.is_success() => Ok(response),
+            StatusCode::NOT_FOUND => Err(PolarError::NotFound),
+            _ => Err(PolarError::Unknown(response.text().await?)),
+        }
+    }
+}
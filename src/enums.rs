@@ -10,7 +10,7 @@ pub enum AmountType {
     MeteredUnit,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum BenefitType {
     Custom,
@@ -21,6 +21,23 @@ pub enum BenefitType {
     MeterCredit,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BenefitsSorting {
+    CreatedAt,
+    #[serde(rename = "-created_at")]
+    CreatedAtDesc,
+    Description,
+    #[serde(rename = "-description")]
+    DescriptionDesc,
+    Type,
+    #[serde(rename = "-type")]
+    TypeDesc,
+    UserOrder,
+    #[serde(rename = "-user_order")]
+    UserOrderDesc,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum BillingAddressField {
@@ -29,6 +46,26 @@ pub enum BillingAddressField {
     Disabled,
 }
 
+/// Brand of a card, as reported by the payment processor.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CardBrand {
+    Amex,
+    CartesBancaires,
+    Diners,
+    Discover,
+    EftposAu,
+    Interac,
+    Jcb,
+    Link,
+    Mastercard,
+    Unionpay,
+    Visa,
+    /// A brand unknown to the crate.
+    #[serde(other)]
+    Unknown,
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CheckoutSessionsSorting {
@@ -59,7 +96,7 @@ impl CheckoutSessionStatus {
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CustomerCancellationReason {
     CustomerService,
@@ -72,6 +109,20 @@ pub enum CustomerCancellationReason {
     Other,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomersSorting {
+    CreatedAt,
+    #[serde(rename = "-created_at")]
+    CreatedAtDesc,
+    Email,
+    #[serde(rename = "-email")]
+    EmailDesc,
+    Name,
+    #[serde(rename = "-name")]
+    NameDesc,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CustomFieldType {
@@ -82,7 +133,7 @@ pub enum CustomFieldType {
     Select,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DiscountDuration {
     Once,
@@ -90,13 +141,48 @@ pub enum DiscountDuration {
     Repeating,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DiscountType {
     Fixed,
     Percentage,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscountsSorting {
+    CreatedAt,
+    #[serde(rename = "-created_at")]
+    CreatedAtDesc,
+    Name,
+    #[serde(rename = "-name")]
+    NameDesc,
+    Code,
+    #[serde(rename = "-code")]
+    CodeDesc,
+    RedemptionsCount,
+    #[serde(rename = "-redemptions_count")]
+    RedemptionsCountDesc,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileServiceType {
+    Downloadable,
+    ProductMedia,
+    OrganizationAvatar,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitHubRepositoryPermission {
+    Pull,
+    Triage,
+    Push,
+    Maintain,
+    Admin,
+}
+
 #[derive(Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Interval {
@@ -107,6 +193,22 @@ pub enum Interval {
     Hour,
 }
 
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LicenseKeyExpirationTimeframe {
+    Year,
+    Month,
+    Day,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LicenseKeyStatus {
+    Granted,
+    Revoked,
+    Disabled,
+}
+
 #[derive(Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum MeterAggregationFunc {
@@ -148,12 +250,183 @@ pub enum MetersSorting {
     NameDesc,
 }
 
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderBillingReason {
+    Purchase,
+    SubscriptionCreate,
+    SubscriptionCycle,
+    SubscriptionUpdate,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrdersSorting {
+    CreatedAt,
+    #[serde(rename = "-created_at")]
+    CreatedAtDesc,
+    Status,
+    #[serde(rename = "-status")]
+    StatusDesc,
+    InvoiceNumber,
+    #[serde(rename = "-invoice_number")]
+    InvoiceNumberDesc,
+    Amount,
+    #[serde(rename = "-amount")]
+    AmountDesc,
+    NetAmount,
+    #[serde(rename = "-net_amount")]
+    NetAmountDesc,
+    Customer,
+    #[serde(rename = "-customer")]
+    CustomerDesc,
+    Product,
+    #[serde(rename = "-product")]
+    ProductDesc,
+    Discount,
+    #[serde(rename = "-discount")]
+    DiscountDesc,
+    Subscription,
+    #[serde(rename = "-subscription")]
+    SubscriptionDesc,
+}
+
+/// Status of an order.
+///
+/// Orders move through the following states, refunds never go back to a previous state:
+///
+/// ```text
+/// pending ──► paid ──► partially_refunded ──► refunded
+///               │                ▲   │          ▲
+///               │                └───┘          │
+///               └───────────────────────────────┘
+/// ```
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    Pending,
+    Paid,
+    Refunded,
+    PartiallyRefunded,
+}
+
+impl OrderStatus {
+    /// Whether the order is paid and nothing was refunded. This is the state fulfillment should be triggered on.
+    pub fn is_paid(&self) -> bool {
+        *self == Self::Paid
+    }
+
+    /// Whether the order was fully refunded.
+    pub fn is_refunded(&self) -> bool {
+        *self == Self::Refunded
+    }
+
+    /// Whether part of the order amount was refunded.
+    pub fn is_partially_refunded(&self) -> bool {
+        *self == Self::PartiallyRefunded
+    }
+
+    /// Whether an order can move from this status to `next`, following the diagram above.
+    ///
+    /// A partially refunded order can be partially refunded again.
+    pub fn can_transition_to(&self, next: OrderStatus) -> bool {
+        matches!(
+            (self, next),
+            (Self::Pending, Self::Paid)
+                | (Self::Paid, Self::PartiallyRefunded)
+                | (Self::Paid, Self::Refunded)
+                | (Self::PartiallyRefunded, Self::PartiallyRefunded)
+                | (Self::PartiallyRefunded, Self::Refunded)
+        )
+    }
+}
+
+/// Broad reason of a declined payment, e.g. to pick the copy of a dunning email.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PaymentDeclineCategory {
+    /// Not enough funds or credit, retrying later may succeed.
+    InsufficientFunds,
+    /// The card expired, the customer must update it.
+    ExpiredCard,
+    /// The card details are wrong or the card can't be used, the customer must update it.
+    InvalidCard,
+    /// The customer must authenticate the payment, e.g. with 3D Secure.
+    AuthenticationRequired,
+    /// The issuer flagged the payment as fraudulent, or the card as lost or stolen. Don't ask for a retry.
+    Fraud,
+    /// Declined without a specific reason, or a temporary error of the issuer.
+    Other,
+}
+
+/// Reason of a declined payment, as reported by the payment processor.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentDeclineReason {
+    AuthenticationRequired,
+    CardNotSupported,
+    CardVelocityExceeded,
+    CurrencyNotSupported,
+    DoNotHonor,
+    ExpiredCard,
+    Fraudulent,
+    GenericDecline,
+    IncorrectCvc,
+    IncorrectNumber,
+    IncorrectZip,
+    InsufficientFunds,
+    InvalidAccount,
+    InvalidExpiryMonth,
+    InvalidExpiryYear,
+    LostCard,
+    PickupCard,
+    ProcessingError,
+    StolenCard,
+    TryAgainLater,
+    WithdrawalCountLimitExceeded,
+    /// A reason unknown to the crate.
+    #[serde(other)]
+    Unknown,
+}
+
+impl PaymentDeclineReason {
+    /// Broad category of the reason, e.g. to tell whether the customer must update their card.
+    pub fn category(&self) -> PaymentDeclineCategory {
+        match self {
+            Self::CardVelocityExceeded | Self::InsufficientFunds | Self::WithdrawalCountLimitExceeded => {
+                PaymentDeclineCategory::InsufficientFunds
+            }
+            Self::ExpiredCard => PaymentDeclineCategory::ExpiredCard,
+            Self::CardNotSupported
+            | Self::CurrencyNotSupported
+            | Self::IncorrectCvc
+            | Self::IncorrectNumber
+            | Self::IncorrectZip
+            | Self::InvalidAccount
+            | Self::InvalidExpiryMonth
+            | Self::InvalidExpiryYear => PaymentDeclineCategory::InvalidCard,
+            Self::AuthenticationRequired => PaymentDeclineCategory::AuthenticationRequired,
+            Self::Fraudulent | Self::LostCard | Self::PickupCard | Self::StolenCard => PaymentDeclineCategory::Fraud,
+            Self::DoNotHonor | Self::GenericDecline | Self::ProcessingError | Self::TryAgainLater | Self::Unknown => {
+                PaymentDeclineCategory::Other
+            }
+        }
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum PaymentProcessor {
     Stripe,
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStatus {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum PriceType {
@@ -161,6 +434,31 @@ pub enum PriceType {
     Recurring,
 }
 
+/// How a product is billed, see [`Product::billing_type`](crate::Product::billing_type).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProductBillingType {
+    OneTime,
+    Recurring(RecurringInterval),
+}
+
+impl ProductBillingType {
+    pub fn is_one_time(&self) -> bool {
+        matches!(self, Self::OneTime)
+    }
+
+    pub fn is_recurring(&self) -> bool {
+        matches!(self, Self::Recurring(_))
+    }
+
+    /// The recurring interval, `None` for one-time purchases.
+    pub fn interval(&self) -> Option<RecurringInterval> {
+        match self {
+            Self::OneTime => None,
+            Self::Recurring(interval) => Some(*interval),
+        }
+    }
+}
+
 #[derive(Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ProductsSorting {
@@ -185,7 +483,7 @@ pub enum ProrationBehavior {
     Prorate,
 }
 
-#[derive(Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum RecurringInterval {
     Day,
@@ -231,3 +529,500 @@ pub enum SubscriptionStatus {
     Canceled,
     Unpaid,
 }
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum WebhookEventType {
+    #[serde(rename = "checkout.created")]
+    CheckoutCreated,
+    #[serde(rename = "checkout.updated")]
+    CheckoutUpdated,
+    #[serde(rename = "customer.created")]
+    CustomerCreated,
+    #[serde(rename = "customer.updated")]
+    CustomerUpdated,
+    #[serde(rename = "customer.deleted")]
+    CustomerDeleted,
+    #[serde(rename = "customer.state_changed")]
+    CustomerStateChanged,
+    #[serde(rename = "order.created")]
+    OrderCreated,
+    #[serde(rename = "order.updated")]
+    OrderUpdated,
+    #[serde(rename = "order.paid")]
+    OrderPaid,
+    #[serde(rename = "order.refunded")]
+    OrderRefunded,
+    #[serde(rename = "refund.created")]
+    RefundCreated,
+    #[serde(rename = "refund.updated")]
+    RefundUpdated,
+    #[serde(rename = "subscription.created")]
+    SubscriptionCreated,
+    #[serde(rename = "subscription.updated")]
+    SubscriptionUpdated,
+    #[serde(rename = "subscription.active")]
+    SubscriptionActive,
+    #[serde(rename = "subscription.canceled")]
+    SubscriptionCanceled,
+    #[serde(rename = "subscription.uncanceled")]
+    SubscriptionUncanceled,
+    #[serde(rename = "subscription.revoked")]
+    SubscriptionRevoked,
+    #[serde(rename = "product.created")]
+    ProductCreated,
+    #[serde(rename = "product.updated")]
+    ProductUpdated,
+    #[serde(rename = "benefit.created")]
+    BenefitCreated,
+    #[serde(rename = "benefit.updated")]
+    BenefitUpdated,
+    #[serde(rename = "benefit_grant.created")]
+    BenefitGrantCreated,
+    #[serde(rename = "benefit_grant.cycled")]
+    BenefitGrantCycled,
+    #[serde(rename = "benefit_grant.updated")]
+    BenefitGrantUpdated,
+    #[serde(rename = "benefit_grant.revoked")]
+    BenefitGrantRevoked,
+    #[serde(rename = "organization.updated")]
+    OrganizationUpdated,
+    /// An event type unknown to the crate, e.g. one added to the API since this release. Only meant to be received.
+    #[serde(other, rename = "unknown")]
+    Unknown,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    Raw,
+    Discord,
+    Slack,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, from_value, to_value};
+
+    use super::*;
+
+    /// Asserts that every variant of an enum maps to the exact string expected by the API.
+    ///
+    /// The generated `match` is exhaustive, so adding a variant without listing it here fails to compile.
+    macro_rules! assert_enum_strings {
+        ($test:ident, $enum:ident, [$($mode:ident),+], { $($variant:ident => $value:literal),+ $(,)? }) => {
+            #[test]
+            fn $test() {
+                fn expected(variant: &$enum) -> &'static str {
+                    match variant {
+                        $($enum::$variant => $value,)+
+                    }
+                }
+
+                for variant in [$($enum::$variant),+] {
+                    $(assert_enum_strings!(@$mode $enum, variant);)+
+                }
+            }
+        };
+        (@serialize $enum:ident, $variant:ident) => {
+            assert_eq!(to_value(&$variant).unwrap(), Value::from(expected(&$variant)));
+        };
+        (@deserialize $enum:ident, $variant:ident) => {
+            let parsed: $enum = from_value(Value::from(expected(&$variant))).unwrap();
+
+            assert_eq!(expected(&parsed), expected(&$variant));
+        };
+    }
+
+    assert_enum_strings!(should_map_amount_type, AmountType, [serialize, deserialize], {
+        Fixed => "fixed",
+        Custom => "custom",
+        Free => "free",
+        MeteredUnit => "metered_unit",
+    });
+
+    assert_enum_strings!(should_map_benefit_type, BenefitType, [serialize, deserialize], {
+        Custom => "custom",
+        Discord => "discord",
+        GithubRepository => "github_repository",
+        Downloadables => "downloadables",
+        LicenseKeys => "license_keys",
+        MeterCredit => "meter_credit",
+    });
+
+    assert_enum_strings!(should_map_benefits_sorting, BenefitsSorting, [serialize], {
+        CreatedAt => "created_at",
+        CreatedAtDesc => "-created_at",
+        Description => "description",
+        DescriptionDesc => "-description",
+        Type => "type",
+        TypeDesc => "-type",
+        UserOrder => "user_order",
+        UserOrderDesc => "-user_order",
+    });
+
+    assert_enum_strings!(should_map_billing_address_field, BillingAddressField, [deserialize], {
+        Required => "required",
+        Optional => "optional",
+        Disabled => "disabled",
+    });
+
+    assert_enum_strings!(should_map_card_brand, CardBrand, [deserialize], {
+        Amex => "amex",
+        CartesBancaires => "cartes_bancaires",
+        Diners => "diners",
+        Discover => "discover",
+        EftposAu => "eftpos_au",
+        Interac => "interac",
+        Jcb => "jcb",
+        Link => "link",
+        Mastercard => "mastercard",
+        Unionpay => "unionpay",
+        Visa => "visa",
+        Unknown => "unknown",
+    });
+
+    assert_enum_strings!(should_map_checkout_sessions_sorting, CheckoutSessionsSorting, [serialize], {
+        CreatedAt => "created_at",
+        CreatedAtDesc => "-created_at",
+        ExpiresAt => "expires_at",
+        ExpiresAtDesc => "-expires_at",
+        Status => "status",
+        StatusDesc => "-status",
+    });
+
+    assert_enum_strings!(should_map_checkout_session_status, CheckoutSessionStatus, [serialize, deserialize], {
+        Open => "open",
+        Expired => "expired",
+        Confirmed => "confirmed",
+        Succeeded => "succeeded",
+        Failed => "failed",
+    });
+
+    assert_enum_strings!(should_map_customer_cancellation_reason, CustomerCancellationReason, [serialize, deserialize], {
+        CustomerService => "customer_service",
+        LowQuality => "low_quality",
+        MissingFeatures => "missing_features",
+        SwitchedService => "switched_service",
+        TooComplex => "too_complex",
+        TooExpensive => "too_expensive",
+        Unused => "unused",
+        Other => "other",
+    });
+
+    assert_enum_strings!(should_map_customers_sorting, CustomersSorting, [serialize], {
+        CreatedAt => "created_at",
+        CreatedAtDesc => "-created_at",
+        Email => "email",
+        EmailDesc => "-email",
+        Name => "name",
+        NameDesc => "-name",
+    });
+
+    assert_enum_strings!(should_map_custom_field_type, CustomFieldType, [deserialize], {
+        Text => "text",
+        Number => "number",
+        Date => "date",
+        Checkbox => "checkbox",
+        Select => "select",
+    });
+
+    assert_enum_strings!(should_map_discount_duration, DiscountDuration, [serialize, deserialize], {
+        Once => "once",
+        Forever => "forever",
+        Repeating => "repeating",
+    });
+
+    assert_enum_strings!(should_map_discount_type, DiscountType, [serialize, deserialize], {
+        Fixed => "fixed",
+        Percentage => "percentage",
+    });
+
+    assert_enum_strings!(should_map_discounts_sorting, DiscountsSorting, [serialize], {
+        CreatedAt => "created_at",
+        CreatedAtDesc => "-created_at",
+        Name => "name",
+        NameDesc => "-name",
+        Code => "code",
+        CodeDesc => "-code",
+        RedemptionsCount => "redemptions_count",
+        RedemptionsCountDesc => "-redemptions_count",
+    });
+
+    assert_enum_strings!(should_map_file_service_type, FileServiceType, [serialize, deserialize], {
+        Downloadable => "downloadable",
+        ProductMedia => "product_media",
+        OrganizationAvatar => "organization_avatar",
+    });
+
+    assert_enum_strings!(should_map_github_repository_permission, GitHubRepositoryPermission, [serialize, deserialize], {
+        Pull => "pull",
+        Triage => "triage",
+        Push => "push",
+        Maintain => "maintain",
+        Admin => "admin",
+    });
+
+    assert_enum_strings!(should_map_interval, Interval, [serialize, deserialize], {
+        Year => "year",
+        Month => "month",
+        Week => "week",
+        Day => "day",
+        Hour => "hour",
+    });
+
+    assert_enum_strings!(should_map_license_key_expiration_timeframe, LicenseKeyExpirationTimeframe, [serialize, deserialize], {
+        Year => "year",
+        Month => "month",
+        Day => "day",
+    });
+
+    assert_enum_strings!(should_map_license_key_status, LicenseKeyStatus, [serialize, deserialize], {
+        Granted => "granted",
+        Revoked => "revoked",
+        Disabled => "disabled",
+    });
+
+    assert_enum_strings!(should_map_meter_aggregation_func, MeterAggregationFunc, [serialize, deserialize], {
+        Count => "count",
+        Sum => "sum",
+        Max => "max",
+        Min => "min",
+        Avg => "avg",
+    });
+
+    assert_enum_strings!(should_map_meter_filter_conjunction, MeterFilterConjunction, [serialize, deserialize], {
+        And => "and",
+        Or => "or",
+    });
+
+    assert_enum_strings!(should_map_meter_filter_operator, MeterFilterOperator, [serialize, deserialize], {
+        Eq => "eq",
+        Ne => "ne",
+        Gt => "gt",
+        Gte => "gte",
+        Lt => "lt",
+        Lte => "lte",
+        Like => "like",
+        NotLike => "not_like",
+    });
+
+    assert_enum_strings!(should_map_meters_sorting, MetersSorting, [serialize], {
+        CreatedAt => "created_at",
+        CreatedAtDesc => "-created_at",
+        Name => "name",
+        NameDesc => "-name",
+    });
+
+    assert_enum_strings!(should_map_order_billing_reason, OrderBillingReason, [serialize, deserialize], {
+        Purchase => "purchase",
+        SubscriptionCreate => "subscription_create",
+        SubscriptionCycle => "subscription_cycle",
+        SubscriptionUpdate => "subscription_update",
+    });
+
+    assert_enum_strings!(should_map_orders_sorting, OrdersSorting, [serialize], {
+        CreatedAt => "created_at",
+        CreatedAtDesc => "-created_at",
+        Status => "status",
+        StatusDesc => "-status",
+        InvoiceNumber => "invoice_number",
+        InvoiceNumberDesc => "-invoice_number",
+        Amount => "amount",
+        AmountDesc => "-amount",
+        NetAmount => "net_amount",
+        NetAmountDesc => "-net_amount",
+        Customer => "customer",
+        CustomerDesc => "-customer",
+        Product => "product",
+        ProductDesc => "-product",
+        Discount => "discount",
+        DiscountDesc => "-discount",
+        Subscription => "subscription",
+        SubscriptionDesc => "-subscription",
+    });
+
+    assert_enum_strings!(should_map_order_status, OrderStatus, [serialize, deserialize], {
+        Pending => "pending",
+        Paid => "paid",
+        Refunded => "refunded",
+        PartiallyRefunded => "partially_refunded",
+    });
+
+    assert_enum_strings!(should_map_payment_decline_reason, PaymentDeclineReason, [deserialize], {
+        AuthenticationRequired => "authentication_required",
+        CardNotSupported => "card_not_supported",
+        CardVelocityExceeded => "card_velocity_exceeded",
+        CurrencyNotSupported => "currency_not_supported",
+        DoNotHonor => "do_not_honor",
+        ExpiredCard => "expired_card",
+        Fraudulent => "fraudulent",
+        GenericDecline => "generic_decline",
+        IncorrectCvc => "incorrect_cvc",
+        IncorrectNumber => "incorrect_number",
+        IncorrectZip => "incorrect_zip",
+        InsufficientFunds => "insufficient_funds",
+        InvalidAccount => "invalid_account",
+        InvalidExpiryMonth => "invalid_expiry_month",
+        InvalidExpiryYear => "invalid_expiry_year",
+        LostCard => "lost_card",
+        PickupCard => "pickup_card",
+        ProcessingError => "processing_error",
+        StolenCard => "stolen_card",
+        TryAgainLater => "try_again_later",
+        WithdrawalCountLimitExceeded => "withdrawal_count_limit_exceeded",
+        Unknown => "unknown",
+    });
+
+    assert_enum_strings!(should_map_payment_processor, PaymentProcessor, [deserialize], {
+        Stripe => "stripe",
+    });
+
+    assert_enum_strings!(should_map_payment_status, PaymentStatus, [serialize, deserialize], {
+        Pending => "pending",
+        Succeeded => "succeeded",
+        Failed => "failed",
+    });
+
+    assert_enum_strings!(should_map_price_type, PriceType, [deserialize], {
+        OneTime => "one_time",
+        Recurring => "recurring",
+    });
+
+    assert_enum_strings!(should_map_products_sorting, ProductsSorting, [serialize], {
+        CreatedAt => "created_at",
+        CreatedAtDesc => "-created_at",
+        Name => "name",
+        NameDesc => "-name",
+        PriceAmountType => "price_amount_type",
+        PriceAmountTypeDesc => "-price_amount_type",
+        PriceAmount => "price_amount",
+        PriceAmountDesc => "-price_amount",
+    });
+
+    assert_enum_strings!(should_map_proration_behavior, ProrationBehavior, [serialize, deserialize], {
+        Invoice => "invoice",
+        Prorate => "prorate",
+    });
+
+    assert_enum_strings!(should_map_recurring_interval, RecurringInterval, [serialize, deserialize], {
+        Day => "day",
+        Week => "week",
+        Month => "month",
+        Year => "year",
+    });
+
+    assert_enum_strings!(should_map_subscriptions_sorting, SubscriptionsSorting, [serialize], {
+        Customer => "customer",
+        CustomerDesc => "-customer",
+        Status => "status",
+        StatusDesc => "-status",
+        StartedAt => "started_at",
+        StartedAtDesc => "-started_at",
+        CurrentPeriodEnd => "current_period_end",
+        CurrentPeriodEndDesc => "-current_period_end",
+        Amount => "amount",
+        AmountDesc => "-amount",
+        Product => "product",
+        ProductDesc => "-product",
+        Discount => "discount",
+        DiscountDesc => "-discount",
+    });
+
+    assert_enum_strings!(should_map_subscription_status, SubscriptionStatus, [deserialize], {
+        Incomplete => "incomplete",
+        IncompleteExpired => "incomplete_expired",
+        Trialing => "trialing",
+        Active => "active",
+        PastDue => "past_due",
+        Canceled => "canceled",
+        Unpaid => "unpaid",
+    });
+
+    assert_enum_strings!(should_map_webhook_event_type, WebhookEventType, [serialize, deserialize], {
+        CheckoutCreated => "checkout.created",
+        CheckoutUpdated => "checkout.updated",
+        CustomerCreated => "customer.created",
+        CustomerUpdated => "customer.updated",
+        CustomerDeleted => "customer.deleted",
+        CustomerStateChanged => "customer.state_changed",
+        OrderCreated => "order.created",
+        OrderUpdated => "order.updated",
+        OrderPaid => "order.paid",
+        OrderRefunded => "order.refunded",
+        RefundCreated => "refund.created",
+        RefundUpdated => "refund.updated",
+        SubscriptionCreated => "subscription.created",
+        SubscriptionUpdated => "subscription.updated",
+        SubscriptionActive => "subscription.active",
+        SubscriptionCanceled => "subscription.canceled",
+        SubscriptionUncanceled => "subscription.uncanceled",
+        SubscriptionRevoked => "subscription.revoked",
+        ProductCreated => "product.created",
+        ProductUpdated => "product.updated",
+        BenefitCreated => "benefit.created",
+        BenefitUpdated => "benefit.updated",
+        BenefitGrantCreated => "benefit_grant.created",
+        BenefitGrantCycled => "benefit_grant.cycled",
+        BenefitGrantUpdated => "benefit_grant.updated",
+        BenefitGrantRevoked => "benefit_grant.revoked",
+        OrganizationUpdated => "organization.updated",
+        Unknown => "unknown",
+    });
+
+    assert_enum_strings!(should_map_webhook_format, WebhookFormat, [serialize, deserialize], {
+        Raw => "raw",
+        Discord => "discord",
+        Slack => "slack",
+    });
+
+    #[test]
+    fn should_allow_order_status_transitions() {
+        assert!(OrderStatus::Pending.can_transition_to(OrderStatus::Paid));
+        assert!(OrderStatus::Paid.can_transition_to(OrderStatus::PartiallyRefunded));
+        assert!(OrderStatus::Paid.can_transition_to(OrderStatus::Refunded));
+        assert!(OrderStatus::PartiallyRefunded.can_transition_to(OrderStatus::PartiallyRefunded));
+        assert!(OrderStatus::PartiallyRefunded.can_transition_to(OrderStatus::Refunded));
+    }
+
+    #[test]
+    fn should_not_allow_order_status_transitions() {
+        assert!(!OrderStatus::Pending.can_transition_to(OrderStatus::Refunded));
+        assert!(!OrderStatus::Paid.can_transition_to(OrderStatus::Pending));
+        assert!(!OrderStatus::Paid.can_transition_to(OrderStatus::Paid));
+        assert!(!OrderStatus::Refunded.can_transition_to(OrderStatus::PartiallyRefunded));
+        assert!(!OrderStatus::Refunded.can_transition_to(OrderStatus::Paid));
+    }
+
+    #[test]
+    fn should_map_unknown_values() {
+        assert_eq!(
+            from_value::<CardBrand>(Value::from("new_brand")).unwrap(),
+            CardBrand::Unknown
+        );
+        assert_eq!(
+            from_value::<PaymentDeclineReason>(Value::from("new_reason")).unwrap(),
+            PaymentDeclineReason::Unknown
+        );
+    }
+
+    #[test]
+    fn should_categorize_payment_decline_reasons() {
+        assert_eq!(
+            PaymentDeclineReason::InsufficientFunds.category(),
+            PaymentDeclineCategory::InsufficientFunds
+        );
+        assert_eq!(
+            PaymentDeclineReason::ExpiredCard.category(),
+            PaymentDeclineCategory::ExpiredCard
+        );
+        assert_eq!(
+            PaymentDeclineReason::IncorrectCvc.category(),
+            PaymentDeclineCategory::InvalidCard
+        );
+        assert_eq!(
+            PaymentDeclineReason::StolenCard.category(),
+            PaymentDeclineCategory::Fraud
+        );
+        assert_eq!(PaymentDeclineReason::Unknown.category(), PaymentDeclineCategory::Other);
+    }
+}
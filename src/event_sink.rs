@@ -0,0 +1,278 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use futures_sink::Sink;
+
+use crate::{EventParams, Polar, PolarError, PolarResult, ShutdownFuture, Subsystem};
+
+/// Sends a batch, giving its events back along with the result so they can be kept when it fails.
+type IngestFuture<'a> = Pin<Box<dyn Future<Output = (Vec<EventParams<'static>>, PolarResult<i64>)> + Send + 'a>>;
+
+/// A [`Sink`] ingesting events in batches, to plug [`Polar::ingest_events`] at the end of a stream of usage records.
+///
+/// Events are sent once `batch_size` of them are buffered, while the next batch fills up. The sink isn't ready again
+/// until the previous batch is sent, so a slow API slows the stream down instead of buffering without bound. Flushing
+/// or closing the sink sends the remaining events.
+///
+/// When a batch fails to be sent, the error is returned and its events are kept ahead of the buffered ones: flushing
+/// again retries them, and [`EventSink::take_pending`] takes them out, e.g. to store them until the API is back.
+pub struct EventSink<'a> {
+    polar: &'a Polar,
+    batch_size: usize,
+    buffer: Vec<EventParams<'static>>,
+    in_flight: Option<IngestFuture<'a>>,
+    inserted: i64,
+}
+
+impl<'a> EventSink<'a> {
+    pub fn new(polar: &'a Polar, batch_size: usize) -> Self {
+        let batch_size = batch_size.max(1);
+
+        Self {
+            polar,
+            batch_size,
+            buffer: Vec::with_capacity(batch_size),
+            in_flight: None,
+            inserted: 0,
+        }
+    }
+
+    /// Number of events inserted so far, as reported by the API.
+    pub fn inserted(&self) -> i64 {
+        self.inserted
+    }
+
+    /// Takes out the events not sent yet, the ones of a failed batch first. A batch being sent isn't included.
+    pub fn take_pending(&mut self) -> Vec<EventParams<'static>> {
+        std::mem::replace(&mut self.buffer, Vec::with_capacity(self.batch_size))
+    }
+
+    fn start_batch(&mut self) {
+        let rest = self.buffer.split_off(self.batch_size.min(self.buffer.len()));
+        let events = std::mem::replace(&mut self.buffer, rest);
+        let polar = self.polar;
+
+        self.in_flight = Some(Box::pin(async move {
+            let result = polar.ingest_events(&events).await;
+
+            (events, result)
+        }));
+    }
+
+    fn poll_in_flight(&mut self, cx: &mut Context<'_>) -> Poll<PolarResult<()>> {
+        if let Some(in_flight) = &mut self.in_flight {
+            let (events, result) = ready!(in_flight.as_mut().poll(cx));
+
+            self.in_flight = None;
+
+            match result {
+                Ok(inserted) => self.inserted += inserted,
+                Err(err) => {
+                    self.buffer.splice(0..0, events);
+
+                    return Poll::Ready(Err(err));
+                }
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Sink<EventParams<'static>> for EventSink<'_> {
+    type Error = PolarError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<PolarResult<()>> {
+        let this = self.get_mut();
+
+        // The batch being sent only progresses when polled, so it's driven while the next one fills up.
+        match this.poll_in_flight(cx) {
+            Poll::Ready(result) => result?,
+            Poll::Pending if this.buffer.len() < this.batch_size => return Poll::Ready(Ok(())),
+            Poll::Pending => return Poll::Pending,
+        }
+
+        if this.buffer.len() >= this.batch_size {
+            this.start_batch();
+
+            if let Poll::Ready(result) = this.poll_in_flight(cx) {
+                result?;
+            }
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, event: EventParams<'static>) -> PolarResult<()> {
+        self.get_mut().buffer.push(event);
+
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<PolarResult<()>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_in_flight(cx))?;
+
+        while !this.buffer.is_empty() {
+            this.start_batch();
+            ready!(this.poll_in_flight(cx))?;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<PolarResult<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl Subsystem for EventSink<'_> {
+    fn name(&self) -> &str {
+        "event_sink"
+    }
+
+    /// Sends the buffered events, like closing the sink.
+    fn shutdown(&mut self) -> ShutdownFuture<'_> {
+        Box::pin(std::future::poll_fn(|cx| Pin::new(&mut *self).poll_close(cx)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::Utc;
+    use futures::{SinkExt, StreamExt, stream};
+    use serde_json::json;
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_ingest_events_in_batches() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/events/ingest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "inserted": 2 })))
+            .expect(3)
+            .mount(&mock)
+            .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+        let mut sink = EventSink::new(&polar, 2);
+        let mut events = stream::iter(0..5).map(|_| {
+            Ok(EventParams {
+                name: "api_call".into(),
+                customer_id: None,
+                metadata: HashMap::new(),
+                timestamp: Utc::now(),
+                organization_id: None,
+            })
+        });
+
+        sink.send_all(&mut events).await.unwrap();
+        sink.close().await.unwrap();
+
+        assert_eq!(sink.inserted(), 6);
+    }
+
+    #[tokio::test]
+    async fn should_ingest_pending_events_on_shutdown() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/events/ingest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "inserted": 1 })))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+        let mut sink = EventSink::new(&polar, 10);
+
+        sink.feed(EventParams {
+            name: "api_call".into(),
+            customer_id: None,
+            metadata: HashMap::new(),
+            timestamp: Utc::now(),
+            organization_id: None,
+        })
+        .await
+        .unwrap();
+
+        let result = crate::shutdown_all(&mut [&mut sink]).await;
+
+        assert!(result.is_ok());
+        assert_eq!(sink.inserted(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_not_ingest_events_when_unauthorized() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/events/ingest"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&mock)
+            .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+        let mut sink = EventSink::new(&polar, 10);
+
+        let result = sink
+            .send(EventParams {
+                name: "api_call".into(),
+                customer_id: None,
+                metadata: HashMap::new(),
+                timestamp: Utc::now(),
+                organization_id: None,
+            })
+            .await;
+
+        assert!(matches!(result, Err(PolarError::Unauthorized)));
+        assert_eq!(sink.take_pending().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_retry_failed_batch_on_flush() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/events/ingest"))
+            .respond_with(ResponseTemplate::new(400))
+            .up_to_n_times(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/events/ingest"))
+            .and(matchers::body_partial_json(
+                json!({ "events": [{ "name": "first" }, { "name": "second" }] }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "inserted": 2 })))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+        let mut sink = EventSink::new(&polar, 10);
+        let event = |name: &'static str| EventParams {
+            name: name.into(),
+            customer_id: None,
+            metadata: HashMap::new(),
+            timestamp: Utc::now(),
+            organization_id: None,
+        };
+
+        assert!(sink.send(event("first")).await.is_err());
+
+        sink.feed(event("second")).await.unwrap();
+        sink.flush().await.unwrap();
+
+        assert_eq!(sink.inserted(), 2);
+        assert!(sink.take_pending().is_empty());
+    }
+}
@@ -0,0 +1,221 @@
+//! Entry points of the fuzz targets in `fuzz/`, feeding arbitrary bytes to the code decoding what Polar sends. Requires
+//! the `testing` feature.
+//!
+//! Decoding is panic-free: a response or a webhook request that can't be decoded is reported as an error, whatever
+//! its content. These functions only check that invariant, they ignore the results and panic only if decoding does.
+//!
+//! ```
+//! polar_rs::fuzz::decode_responses(br#"{"id": 1, "items": [}"#);
+//! polar_rs::fuzz::verify_webhooks(b"\xff\xfe");
+//! ```
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::DateTime;
+use hmac::{Hmac, Mac};
+use serde::de::DeserializeOwned;
+use sha2::Sha256;
+
+use crate::{
+    Benefit, CheckoutLink, CheckoutSession, CustomField, Customer, CustomerPortalCustomer, CustomerState, Discount,
+    Downloadable, Event, FileDownloadUrl, FileRead, FileUpload, LicenseKey, LicenseKeyActivation, Meter,
+    MeterQuantities, Order, OrderInvoice, Page, Payment, Product, Subscription, WebhookEndpoint, WebhookHeaders, json,
+    verify_webhook_at,
+};
+
+const WEBHOOK_SECRET: &str = "fuzz-secret";
+const WEBHOOK_ID: &str = "msg_fuzz";
+const WEBHOOK_TIMESTAMP: i64 = 1_700_000_000;
+
+macro_rules! decode_all {
+    ($data:expr, $($model:ty),+ $(,)?) => {
+        $(decode::<$model>($data);)+
+    };
+}
+
+fn decode<T: DeserializeOwned>(data: &[u8]) {
+    let _ = json::from_body::<T>(data.to_vec(), None);
+    let _ = json::from_body::<T>(data.to_vec(), Some(&|_| {}));
+}
+
+/// Decodes `data` as the body of a response, into every model returned by the API, with and without an unknown
+/// field hook.
+pub fn decode_responses(data: &[u8]) {
+    decode_all!(
+        data,
+        Benefit,
+        CheckoutLink,
+        CheckoutSession,
+        CustomField,
+        Customer,
+        CustomerPortalCustomer,
+        CustomerState,
+        Discount,
+        Downloadable,
+        Event,
+        FileDownloadUrl,
+        FileRead,
+        FileUpload,
+        LicenseKey,
+        LicenseKeyActivation,
+        Meter,
+        MeterQuantities,
+        Order,
+        OrderInvoice,
+        Payment,
+        Product,
+        Subscription,
+        WebhookEndpoint,
+        Page<Benefit>,
+        Page<CheckoutSession>,
+        Page<Customer>,
+        Page<Discount>,
+        Page<Downloadable>,
+        Page<FileRead>,
+        Page<LicenseKey>,
+        Page<Meter>,
+        Page<Order>,
+        Page<Payment>,
+        Page<Product>,
+        Page<Subscription>,
+        Page<WebhookEndpoint>,
+    );
+}
+
+/// Verifies `data` as the body of a webhook request, correctly signed so that it's decoded, then decodes its data
+/// into the models sent by webhooks. `data` is also used as each of the headers of a request, to exercise their
+/// parsing.
+pub fn verify_webhooks(data: &[u8]) {
+    let timestamp = WEBHOOK_TIMESTAMP.to_string();
+    let now = DateTime::from_timestamp(WEBHOOK_TIMESTAMP, 0).unwrap_or_default();
+    let mut mac = Hmac::<Sha256>::new_from_slice(WEBHOOK_SECRET.as_bytes()).expect("HMAC accepts keys of any size");
+
+    mac.update(format!("{WEBHOOK_ID}.{timestamp}.").as_bytes());
+    mac.update(data);
+
+    let signature = format!("v1,{}", BASE64.encode(mac.finalize().into_bytes()));
+    let headers = WebhookHeaders {
+        id: WEBHOOK_ID,
+        timestamp: &timestamp,
+        signature: &signature,
+    };
+
+    if let Ok(event) = verify_webhook_at(WEBHOOK_SECRET, &headers, data, now) {
+        let _ = event.data_as::<Benefit>();
+        let _ = event.data_as::<CheckoutSession>();
+        let _ = event.data_as::<Customer>();
+        let _ = event.data_as::<Order>();
+        let _ = event.data_as::<Product>();
+        let _ = event.data_as::<Subscription>();
+    }
+
+    let header = String::from_utf8_lossy(data);
+
+    for headers in [
+        WebhookHeaders { id: &header, ..headers },
+        WebhookHeaders {
+            timestamp: &header,
+            ..headers
+        },
+        WebhookHeaders {
+            signature: &header,
+            ..headers
+        },
+    ] {
+        let _ = verify_webhook_at(WEBHOOK_SECRET, &headers, b"{}", now);
+    }
+
+    let _ = verify_webhook_at(&header, &headers, data, now);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    fn get_fixtures() -> Vec<Vec<u8>> {
+        fs::read_dir("fixtures")
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+            .map(|path| fs::read(path).unwrap())
+            .collect()
+    }
+
+    /// Deterministic bytes, so a failure can be reproduced.
+    fn get_noise(seed: u64) -> impl Iterator<Item = u64> {
+        std::iter::successors(Some(seed.max(1)), |state| {
+            let mut state = *state;
+
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+
+            Some(state)
+        })
+        .skip(1)
+    }
+
+    #[test]
+    fn should_not_panic_on_fixtures() {
+        for fixture in get_fixtures() {
+            decode_responses(&fixture);
+            verify_webhooks(&fixture);
+        }
+    }
+
+    #[test]
+    fn should_not_panic_on_truncated_fixtures() {
+        for fixture in get_fixtures() {
+            for end in (0..fixture.len()).step_by(fixture.len() / 16 + 1) {
+                decode_responses(&fixture[..end]);
+                verify_webhooks(&fixture[..end]);
+            }
+        }
+    }
+
+    #[test]
+    fn should_not_panic_on_mutated_fixtures() {
+        for (index, fixture) in get_fixtures().into_iter().enumerate() {
+            let mut noise = get_noise(index as u64);
+
+            for _ in 0..16 {
+                let mut mutated = fixture.clone();
+
+                for _ in 0..4 {
+                    let position = noise.next().unwrap() as usize % mutated.len();
+
+                    mutated[position] = noise.next().unwrap() as u8;
+                }
+
+                decode_responses(&mutated);
+                verify_webhooks(&mutated);
+            }
+        }
+    }
+
+    #[test]
+    fn should_not_panic_on_malformed_bodies() {
+        let bodies = [
+            b"".to_vec(),
+            b" \n".to_vec(),
+            b"null".to_vec(),
+            b"{".to_vec(),
+            b"{\"id\":".to_vec(),
+            b"\xff\xfe\xfd".to_vec(),
+            b"\"\\ud800\"".to_vec(),
+            b"1e999999".to_vec(),
+            b"-99999999999999999999999999".to_vec(),
+            br#"{"items": [], "pagination": {"total_count": -1, "max_page": 1e400}}"#.to_vec(),
+            br#"{"type": "order.created", "timestamp": "+999999-12-31T23:59:59Z", "data": {}}"#.to_vec(),
+            "[".repeat(100_000).into_bytes(),
+            "{\"a\":".repeat(100_000).into_bytes(),
+        ];
+
+        for body in bodies {
+            decode_responses(&body);
+            verify_webhooks(&body);
+        }
+    }
+}
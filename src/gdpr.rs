@@ -0,0 +1,207 @@
+//! Customer data erasure, e.g. to answer a GDPR "right to be forgotten" request.
+//!
+//! ```no_run
+//! use polar_rs::Polar;
+//! use polar_rs::gdpr;
+//! use uuid::Uuid;
+//!
+//! # async fn run(customer_id: Uuid) -> polar_rs::PolarResult<()> {
+//! let polar = Polar::new("https://sandbox-api.polar.sh/v1/", "<YOUR ACCESS TOKEN>")?;
+//!
+//! let report = gdpr::erase_customer(&polar, customer_id).await?;
+//!
+//! println!("{} orders retained by Polar", report.retained_orders.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::{ListOrdersParams, Polar, PolarError, PolarResult, UpdateCustomerParams};
+
+/// What was erased for a customer, and what Polar retained.
+#[derive(Debug)]
+pub struct ErasureReport {
+    /// The ID of the customer.
+    pub customer_id: Uuid,
+    /// When the erasure started.
+    pub started_at: DateTime<Utc>,
+    /// When the erasure completed.
+    pub completed_at: DateTime<Utc>,
+    /// Whether the metadata, name, billing address and tax ID of the customer were cleared.
+    pub anonymized: bool,
+    /// Whether the customer was deleted.
+    pub deleted: bool,
+    /// Why the customer couldn't be deleted, when the API refused it.
+    pub deletion_error: Option<String>,
+    /// Orders of the customer, retained by Polar with their invoices for accounting and tax purposes.
+    pub retained_orders: Vec<RetainedOrder>,
+}
+
+/// An order retained by Polar after the erasure of its customer.
+#[derive(Debug)]
+pub struct RetainedOrder {
+    /// The ID of the order.
+    pub id: Uuid,
+    /// Creation timestamp of the order.
+    pub created_at: DateTime<Utc>,
+    /// Invoice number of the order.
+    pub invoice_number: String,
+    /// Whether an invoice was generated for the order.
+    pub is_invoice_generated: bool,
+}
+
+/// Erases the data of a customer: their metadata and personal fields are cleared, then the customer is deleted.
+///
+/// The orders are listed beforehand, since Polar keeps them, and their invoices, after the deletion. When the API
+/// refuses to delete the customer, the report tells why instead of failing, the customer being anonymized anyway.
+pub async fn erase_customer(polar: &Polar, customer_id: Uuid) -> PolarResult<ErasureReport> {
+    let started_at = Utc::now();
+    let retained_orders = list_retained_orders(polar, customer_id).await?;
+
+    polar
+        .update_customer(
+            customer_id,
+            &UpdateCustomerParams {
+                metadata: Some(HashMap::new()),
+                name: Some(None),
+                billing_address: Some(None),
+                tax_id: Some(None),
+                ..Default::default()
+            },
+        )
+        .await?;
+
+    let deletion_error = match polar.delete_customer(customer_id).await {
+        Ok(()) => None,
+        Err(PolarError::Validation(msg) | PolarError::Unknown(msg)) => Some(msg),
+        Err(err) => return Err(err),
+    };
+
+    Ok(ErasureReport {
+        customer_id,
+        started_at,
+        completed_at: Utc::now(),
+        anonymized: true,
+        deleted: deletion_error.is_none(),
+        deletion_error,
+        retained_orders,
+    })
+}
+
+async fn list_retained_orders(polar: &Polar, customer_id: Uuid) -> PolarResult<Vec<RetainedOrder>> {
+    let params = ListOrdersParams {
+        customer_id: Some(vec![customer_id]),
+        limit: Some(100),
+        ..Default::default()
+    };
+    let orders = polar.list_orders(&params).await?.collect_items(polar).await?;
+
+    Ok(orders
+        .into_iter()
+        .map(|order| RetainedOrder {
+            id: order.id,
+            created_at: order.created_at,
+            invoice_number: order.invoice_number,
+            is_invoice_generated: order.is_invoice_generated,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    use super::*;
+    use crate::test_support::get_fixture;
+
+    async fn mount_customer(mock: &MockServer, customer_id: Uuid, delete_status: u16) {
+        let path = format!("/customers/{customer_id}");
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/orders"))
+            .and(matchers::query_param("customer_id", customer_id.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": [get_fixture::<Value>("order")],
+                "pagination": { "total_count": 1, "max_page": 1 },
+            })))
+            .mount(mock)
+            .await;
+
+        Mock::given(matchers::method("PATCH"))
+            .and(matchers::path(&path))
+            .and(matchers::body_json(json!({
+                "metadata": {},
+                "name": null,
+                "billing_address": null,
+                "tax_id": null,
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("customer")))
+            .expect(1)
+            .mount(mock)
+            .await;
+
+        Mock::given(matchers::method("DELETE"))
+            .and(matchers::path(&path))
+            .respond_with(
+                ResponseTemplate::new(delete_status).set_body_json(get_fixture::<Value>("unprocessable_entity")),
+            )
+            .expect(1)
+            .mount(mock)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn should_erase_customer() {
+        let customer_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        mount_customer(&mock, customer_id, 204).await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let report = erase_customer(&polar, customer_id).await.unwrap();
+
+        assert!(report.anonymized && report.deleted);
+        assert_eq!(report.retained_orders.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_report_refused_deletion() {
+        let customer_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        mount_customer(&mock, customer_id, 422).await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let report = erase_customer(&polar, customer_id).await.unwrap();
+
+        assert!(report.anonymized && !report.deleted);
+        assert!(report.deletion_error.is_some());
+    }
+
+    #[tokio::test]
+    async fn should_not_erase_missing_customer() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/orders"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": [],
+                "pagination": { "total_count": 0, "max_page": 0 },
+            })))
+            .mount(&mock)
+            .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let result = erase_customer(&polar, Uuid::new_v4()).await;
+
+        assert!(matches!(result, Err(PolarError::NotFound)));
+    }
+}
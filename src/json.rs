@@ -0,0 +1,372 @@
+use serde::Serialize;
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{DeserializeOwned, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_json::Value;
+
+use crate::{PolarError, PolarResult, UnknownField, UnknownFieldKind};
+
+/// Encodes request bodies and decodes response bodies.
+///
+/// The implementation is picked at compile time, `serde_json` by default or `simd-json` when the feature of the same
+/// name is enabled.
+pub(crate) trait JsonBackend {
+    fn to_vec<T: Serialize>(value: &T) -> PolarResult<Vec<u8>>;
+
+    fn from_vec<T: DeserializeOwned>(bytes: Vec<u8>) -> PolarResult<T>;
+}
+
+#[cfg(not(feature = "simd-json"))]
+pub(crate) struct SerdeJson;
+
+#[cfg(not(feature = "simd-json"))]
+impl JsonBackend for SerdeJson {
+    fn to_vec<T: Serialize>(value: &T) -> PolarResult<Vec<u8>> {
+        Ok(serde_json::to_vec(value)?)
+    }
+
+    fn from_vec<T: DeserializeOwned>(bytes: Vec<u8>) -> PolarResult<T> {
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
+#[cfg(not(feature = "simd-json"))]
+pub(crate) type Json = SerdeJson;
+
+#[cfg(feature = "simd-json")]
+pub(crate) struct SimdJson;
+
+#[cfg(feature = "simd-json")]
+impl JsonBackend for SimdJson {
+    fn to_vec<T: Serialize>(value: &T) -> PolarResult<Vec<u8>> {
+        simd_json::serde::to_vec(value).map_err(|err| PolarError::Request(err.to_string()))
+    }
+
+    fn from_vec<T: DeserializeOwned>(mut bytes: Vec<u8>) -> PolarResult<T> {
+        // simd-json parses in place, hence the owned buffer. The whole input is parsed up front, so trailing data after
+        // the document is rejected there, as `serde_json::Deserializer::end` does.
+        simd_json::serde::from_slice(&mut bytes).map_err(|err| PolarError::Request(err.to_string()))
+    }
+}
+
+#[cfg(feature = "simd-json")]
+pub(crate) type Json = SimdJson;
+
+/// Decodes the body of a response without content, e.g. `204 No Content`, as `null`.
+pub(crate) fn from_empty<T: DeserializeOwned>() -> PolarResult<T> {
+    Json::from_vec(b"null".to_vec()).map_err(|_| PolarError::Request("unexpected empty response body".to_owned()))
+}
+
+/// Decodes the body of a response, an empty body being decoded as `null` so it can be read as `()` or `Option<T>`.
+///
+/// When given, `on_unknown` is called with every field and enum value of the body unknown to the crate. The body is
+/// parsed to a [`Value`] first, so they can be reported with their raw value.
+pub(crate) fn from_body<T: DeserializeOwned>(
+    bytes: Vec<u8>,
+    on_unknown: Option<&dyn Fn(&UnknownField)>,
+) -> PolarResult<T> {
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        from_empty()
+    } else if let Some(on_unknown) = on_unknown {
+        let value: Value = Json::from_vec(bytes)?;
+
+        Ok(T::deserialize(Reporting {
+            value: &value,
+            path: String::new(),
+            on_unknown,
+        })?)
+    } else {
+        Json::from_vec(bytes)
+    }
+}
+
+fn join_path(path: &str, segment: &str) -> String {
+    if path.is_empty() {
+        segment.to_owned()
+    } else {
+        format!("{path}.{segment}")
+    }
+}
+
+/// Deserializes a parsed body, reporting the fields ignored by the target type and the enum values it doesn't know.
+///
+/// Values buffered by serde, e.g. the content of internally tagged enums, are deserialized without it, so what they
+/// hold isn't reported.
+struct Reporting<'de> {
+    value: &'de Value,
+    path: String,
+    on_unknown: &'de dyn Fn(&UnknownField),
+}
+
+impl Reporting<'_> {
+    fn report(&self, kind: UnknownFieldKind) {
+        (self.on_unknown)(&UnknownField {
+            path: self.path.clone(),
+            kind,
+            value: self.value.clone(),
+        });
+    }
+}
+
+impl<'de> Deserializer<'de> for Reporting<'de> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Array(items) => visitor.visit_seq(SeqReporting {
+                items: items.iter().enumerate(),
+                path: self.path,
+                on_unknown: self.on_unknown,
+            }),
+            Value::Object(map) => visitor.visit_map(MapReporting {
+                entries: map.iter(),
+                pending: None,
+                path: self.path,
+                on_unknown: self.on_unknown,
+            }),
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if let Value::String(variant) = self.value
+            && !variants.contains(&variant.as_str())
+        {
+            self.report(UnknownFieldKind::Variant);
+        }
+
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.report(UnknownFieldKind::Field);
+
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier
+    }
+}
+
+struct SeqReporting<'de> {
+    items: std::iter::Enumerate<std::slice::Iter<'de, Value>>,
+    path: String,
+    on_unknown: &'de dyn Fn(&UnknownField),
+}
+
+impl<'de> SeqAccess<'de> for SeqReporting<'de> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error> {
+        let Some((index, value)) = self.items.next() else {
+            return Ok(None);
+        };
+
+        seed.deserialize(Reporting {
+            value,
+            path: join_path(&self.path, &index.to_string()),
+            on_unknown: self.on_unknown,
+        })
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct MapReporting<'de> {
+    entries: serde_json::map::Iter<'de>,
+    pending: Option<(&'de String, &'de Value)>,
+    path: String,
+    on_unknown: &'de dyn Fn(&UnknownField),
+}
+
+impl<'de> MapAccess<'de> for MapReporting<'de> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error> {
+        self.pending = self.entries.next();
+
+        match self.pending {
+            Some((key, _)) => seed.deserialize(BorrowedStrDeserializer::new(key)).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, Self::Error> {
+        let (key, value) = self
+            .pending
+            .take()
+            .ok_or_else(|| serde::de::Error::custom("value requested before its key"))?;
+
+        seed.deserialize(Reporting {
+            value,
+            path: join_path(&self.path, key),
+            on_unknown: self.on_unknown,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+
+    use super::*;
+
+    #[test]
+    fn should_encode_and_decode() {
+        let value = json!({ "name": "<string>", "items": [1, 2, 3] });
+
+        let result: Value = Json::from_vec(Json::to_vec(&value).unwrap()).unwrap();
+
+        assert_eq!(result, value);
+    }
+
+    #[test]
+    fn should_decode_empty_body_as_unit() {
+        let result: PolarResult<()> = from_empty();
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn should_decode_blank_body_as_none() {
+        let result: PolarResult<Option<Value>> = from_body(b" \n".to_vec(), None);
+
+        assert!(result.is_ok_and(|value| value.is_none()));
+    }
+
+    #[test]
+    fn should_decode_null_body_as_none() {
+        let result: PolarResult<Option<Value>> = from_body(b"null".to_vec(), None);
+
+        assert!(result.is_ok_and(|value| value.is_none()));
+    }
+
+    #[test]
+    fn should_report_unknown_fields() {
+        #[derive(serde::Deserialize)]
+        struct Known {
+            #[allow(dead_code)]
+            name: String,
+            #[allow(dead_code)]
+            items: Vec<Known>,
+        }
+
+        let body =
+            json!({ "name": "<string>", "new": 1, "items": [{ "name": "<string>", "items": [], "other": true }] });
+        let unknown = std::sync::Mutex::new(Vec::new());
+
+        let result: PolarResult<Known> = from_body(
+            Json::to_vec(&body).unwrap(),
+            Some(&|field: &UnknownField| unknown.lock().unwrap().push(field.clone())),
+        );
+
+        assert!(result.is_ok());
+        assert_eq!(
+            unknown.into_inner().unwrap(),
+            vec![
+                UnknownField {
+                    path: "items.0.other".to_owned(),
+                    kind: UnknownFieldKind::Field,
+                    value: json!(true),
+                },
+                UnknownField {
+                    path: "new".to_owned(),
+                    kind: UnknownFieldKind::Field,
+                    value: json!(1),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_unknown_variants() {
+        #[derive(serde::Deserialize)]
+        struct Known {
+            brands: Vec<crate::CardBrand>,
+            #[allow(dead_code)]
+            status: Option<crate::OrderStatus>,
+        }
+
+        let body = json!({ "brands": ["visa", "new_brand"], "status": null });
+        let unknown = std::sync::Mutex::new(Vec::new());
+
+        let result: PolarResult<Known> = from_body(
+            Json::to_vec(&body).unwrap(),
+            Some(&|field: &UnknownField| unknown.lock().unwrap().push(field.clone())),
+        );
+
+        assert!(result.is_ok_and(|known| known.brands == [crate::CardBrand::Visa, crate::CardBrand::Unknown]));
+
+        let body = json!({ "brands": [], "status": "new_status" });
+
+        let result: PolarResult<Known> = from_body(
+            Json::to_vec(&body).unwrap(),
+            Some(&|field: &UnknownField| unknown.lock().unwrap().push(field.clone())),
+        );
+
+        assert!(result.is_err());
+        assert_eq!(
+            unknown.into_inner().unwrap(),
+            vec![
+                UnknownField {
+                    path: "brands.1".to_owned(),
+                    kind: UnknownFieldKind::Variant,
+                    value: json!("new_brand"),
+                },
+                UnknownField {
+                    path: "status".to_owned(),
+                    kind: UnknownFieldKind::Variant,
+                    value: json!("new_status"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_not_decode_trailing_data() {
+        for body in [&b"{}garbage"[..], b"{} {}", b"[1]]", b"1 2"] {
+            let result: PolarResult<Value> = from_body(body.to_vec(), None);
+
+            assert!(result.is_err());
+
+            let result: PolarResult<Value> = from_body(body.to_vec(), Some(&|_| {}));
+
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn should_not_decode_empty_body_as_struct() {
+        let result: PolarResult<crate::Customer> = from_empty();
+
+        assert!(result.is_err());
+    }
+}
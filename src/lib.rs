@@ -3,22 +3,88 @@
 use regex::Regex;
 use std::error::Error;
 use std::fmt::Display;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 
+use chrono::{DateTime, Utc};
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+use reqwest::header::CONTENT_TYPE;
 use reqwest::{IntoUrl, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
+use url::Url;
 use uuid::Uuid;
 
+pub mod analytics;
+#[cfg(any(test, feature = "testing"))]
+pub mod fuzz;
+pub mod gdpr;
+pub mod prelude;
+pub mod quickstart;
+pub mod saga;
+pub mod sync;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod types;
+pub mod upload;
+
+mod checkout;
+mod customer_portal;
 mod enums;
+#[cfg(not(target_arch = "wasm32"))]
+mod event_sink;
+mod json;
+mod list;
 mod models;
+mod subsystem;
+#[cfg(test)]
+mod test_support;
+mod webhooks;
 
+pub use checkout::*;
+pub use customer_portal::*;
 pub use enums::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use event_sink::*;
+pub use list::*;
 pub use models::*;
+pub use subsystem::*;
+pub use webhooks::*;
+
+use crate::json::{Json, JsonBackend};
+
+/// Characters escaped when using an arbitrary string as a single path segment.
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'\\')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
 
 static REGEX_QUERY_ARRAY: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[[0-9]+\]").unwrap());
 
+fn encode_path_segment(segment: &str) -> String {
+    utf8_percent_encode(segment, PATH_SEGMENT).to_string()
+}
+
+/// Builds the path of a customer addressed by its external ID, e.g. `customers/external/usr_1337`.
+///
+/// Empty IDs and dot segments are rejected, even encoded they would address another resource once the URL is resolved.
+fn customer_external_path(external_id: &str) -> PolarResult<String> {
+    if matches!(external_id, "" | "." | "..") {
+        return Err(PolarError::Request(format!("invalid external ID: {external_id:?}")));
+    }
+
+    Ok(format!("customers/external/{}", encode_path_segment(external_id)))
+}
+
 #[derive(Debug, Deserialize)]
 pub enum PolarError {
     NotFound,
@@ -52,6 +118,12 @@ impl From<reqwest::Error> for PolarError {
     }
 }
 
+impl From<std::io::Error> for PolarError {
+    fn from(err: std::io::Error) -> Self {
+        PolarError::Request(err.to_string())
+    }
+}
+
 impl From<url::ParseError> for PolarError {
     fn from(err: url::ParseError) -> Self {
         PolarError::Request(err.to_string())
@@ -62,9 +134,88 @@ impl Error for PolarError {}
 
 pub type PolarResult<T> = Result<T, PolarError>;
 
+/// A deprecation notice sent by the API along with a response, through the `Deprecation` and `Sunset` headers.
+#[derive(Clone, Debug)]
+pub struct Deprecation {
+    /// HTTP method of the request.
+    pub method: reqwest::Method,
+    /// URL of the request.
+    pub url: Url,
+    /// Value of the `Deprecation` header, e.g. `@1735689600` or `true`.
+    pub deprecation: Option<String>,
+    /// When the endpoint will stop working, from the `Sunset` header.
+    pub sunset: Option<DateTime<Utc>>,
+}
+
+impl Deprecation {
+    fn from_response(method: reqwest::Method, response: &reqwest::Response) -> Option<Self> {
+        let get = |name| response.headers().get(name).and_then(|value| value.to_str().ok());
+        let deprecation = get("deprecation").map(str::to_owned);
+        let sunset = get("sunset")
+            .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+            .map(|sunset| sunset.to_utc());
+
+        if deprecation.is_none() && sunset.is_none() {
+            return None;
+        }
+
+        Some(Self {
+            method,
+            url: response.url().clone(),
+            deprecation,
+            sunset,
+        })
+    }
+}
+
+/// Something in a response body unknown to the crate, reported to the hook set with
+/// [`Polar::with_unknown_field_hook`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnknownField {
+    /// Path of the value in the body, e.g. `prices.0.new_field`.
+    pub path: String,
+    pub kind: UnknownFieldKind,
+    /// The value as sent by the API.
+    pub value: Value,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnknownFieldKind {
+    /// A field the models don't have. It's ignored when decoding.
+    Field,
+    /// An enum value the crate doesn't know. It's decoded as the `Unknown` variant of the enums having one, other
+    /// enums fail to decode.
+    Variant,
+}
+
+/// A cache of customer states, keyed by external customer ID, consulted by [`Polar::customer_has_benefit`] and
+/// [`Polar::customer_has_product`] before calling the API.
+///
+/// Expiring entries is up to the implementation, e.g. after a short TTL or when a `customer.state_changed` webhook
+/// is received. Implementations running background tasks to do so should also implement [`Subsystem`].
+pub trait CustomerStateCache: Send + Sync {
+    fn get(&self, external_id: &str) -> Option<Arc<CustomerState>>;
+
+    fn insert(&self, external_id: &str, state: Arc<CustomerState>);
+}
+
+type DeprecationHook = Arc<dyn Fn(&Deprecation) + Send + Sync>;
+
+type RequestHook = Arc<dyn Fn(&mut reqwest::Request) + Send + Sync>;
+
+type UnknownFieldHook = Arc<dyn Fn(&UnknownField) + Send + Sync>;
+
 pub struct Polar {
     base_url: reqwest::Url,
     access_token: String,
+    client: reqwest::Client,
+    unknown_field_hook: Option<UnknownFieldHook>,
+    deprecation_hook: Option<DeprecationHook>,
+    request_hook: Option<RequestHook>,
+    allowed_hosts: Option<Vec<String>>,
+    customer_state_cache: Option<Arc<dyn CustomerStateCache>>,
+    #[cfg(feature = "gzip")]
+    event_compression_threshold: Option<usize>,
 }
 
 impl Polar {
@@ -86,21 +237,182 @@ impl Polar {
         Ok(Self {
             base_url,
             access_token: access_token.to_string(),
+            client: reqwest::Client::new(),
+            unknown_field_hook: None,
+            deprecation_hook: None,
+            request_hook: None,
+            allowed_hosts: None,
+            customer_state_cache: None,
+            #[cfg(feature = "gzip")]
+            event_compression_threshold: None,
         })
     }
 
+    /// Sets a hook called with every field and enum value of a response unknown to the crate, along with its path and
+    /// raw value.
+    ///
+    /// Unknown fields are ignored when decoding, so this is a way to notice additions to the API before the crate
+    /// supports them. Unknown enum values are reported before failing to decode, unless the enum has an `Unknown`
+    /// variant. Responses are parsed to a [`Value`] first when the hook is set.
+    pub fn with_unknown_field_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&UnknownField) + Send + Sync + 'static,
+    {
+        self.unknown_field_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets a hook called when a response comes with a `Deprecation` or `Sunset` header, meaning the endpoint will
+    /// be removed, e.g. to log a warning naming the endpoint before it breaks.
+    pub fn with_deprecation_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&Deprecation) + Send + Sync + 'static,
+    {
+        self.deprecation_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets a hook called with every request right before it's sent, once its URL, headers and body are final.
+    ///
+    /// This is a way to sign requests for a proxy, e.g. with an HMAC of the body added as a header. The body is
+    /// always in memory, so [`reqwest::Body::as_bytes`] returns it.
+    pub fn with_request_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&mut reqwest::Request) + Send + Sync + 'static,
+    {
+        self.request_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Sets the policy followed when the API answers with a redirection, by default up to 10 redirections are followed.
+    ///
+    /// Use [`RedirectPolicy::none`](crate::types::RedirectPolicy::none) to never follow them. Not available on
+    /// WebAssembly, where redirections are handled by the runtime.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_redirect_policy(mut self, policy: reqwest::redirect::Policy) -> PolarResult<Self> {
+        self.client = reqwest::Client::builder().redirect(policy).build()?;
+
+        Ok(self)
+    }
+
+    /// Restricts requests to the given hosts, e.g. `["api.polar.sh"]`, and to paths under the base URL.
+    ///
+    /// Requests that would go anywhere else, like when an ID taken from user input changes the path, fail with
+    /// [`PolarError::Request`] before being sent. Redirections are not checked, see [`Polar::with_redirect_policy`].
+    pub fn with_allowed_hosts<I, S>(mut self, hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.allowed_hosts = Some(hosts.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the cache of customer states used by [`Polar::customer_has_benefit`] and [`Polar::customer_has_product`].
+    pub fn with_customer_state_cache<C>(mut self, cache: C) -> Self
+    where
+        C: CustomerStateCache + 'static,
+    {
+        self.customer_state_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Compresses the bodies of [`Polar::ingest_events`] requests larger than `threshold` bytes with gzip.
+    ///
+    /// If the API answers with `415 Unsupported Media Type`, the batch is sent again uncompressed.
+    #[cfg(feature = "gzip")]
+    pub fn with_event_compression(mut self, threshold: usize) -> Self {
+        self.event_compression_threshold = Some(threshold);
+        self
+    }
+
+    /// Creates a client for the customer portal API, authenticated with a customer access token, without needing an
+    /// organization client.
+    ///
+    /// The returned [`CustomerPortal`] only exposes customer-scoped endpoints, so organization endpoints can't be
+    /// called with the wrong kind of token.
+    pub fn with_customer_token<U: IntoUrl, T: Display>(base_url: U, token: T) -> PolarResult<CustomerPortal> {
+        Self::new(base_url, token).map(CustomerPortal::new)
+    }
+
+    /// Creates a client for the customer portal API, authenticated with a customer session token, e.g. the one
+    /// created after a checkout confirmation.
+    ///
+    /// The new client shares the base URL and the HTTP connection pool of this one.
+    pub fn customer_portal_from_session_token<T: Display>(&self, token: T) -> PolarResult<CustomerPortal> {
+        let token = token.to_string();
+
+        if token.is_empty() {
+            return Err(PolarError::Request("token cannot be empty".to_owned()));
+        }
+
+        Ok(CustomerPortal::new(Self {
+            base_url: self.base_url.clone(),
+            access_token: token,
+            client: self.client.clone(),
+            unknown_field_hook: self.unknown_field_hook.clone(),
+            deprecation_hook: self.deprecation_hook.clone(),
+            request_hook: self.request_hook.clone(),
+            allowed_hosts: self.allowed_hosts.clone(),
+            customer_state_cache: self.customer_state_cache.clone(),
+            #[cfg(feature = "gzip")]
+            event_compression_threshold: self.event_compression_threshold,
+        }))
+    }
+
+    /// Fetches `path`, relative to the base URL, decoding the response into `T` instead of a model of the crate, e.g.
+    /// a struct with only the fields you need, to keep big responses lean. Authentication, allowed hosts, hooks and
+    /// errors are handled as for the other methods.
+    ///
+    /// ```no_run
+    /// # async fn run(polar: &polar_rs::Polar, id: polar_rs::types::Uuid) -> polar_rs::PolarResult<()> {
+    /// #[derive(serde::Deserialize)]
+    /// struct ProductName {
+    ///     name: String,
+    /// }
+    ///
+    /// let product: ProductName = polar.get_as(&format!("products/{id}")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_as<T>(&self, path: &str) -> PolarResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.get(path).await
+    }
+
+    /// Same as [`Polar::get_as`], with query params, e.g. one of the `List*Params` of the crate.
+    pub async fn get_with_params_as<P, T>(&self, path: &str, params: &P) -> PolarResult<T>
+    where
+        P: Serialize,
+        T: DeserializeOwned,
+    {
+        self.get_with_params(path, params).await
+    }
+
+    /// Same as [`Polar::get_with_params_as`] for list endpoints, decoding the items into `T`. The returned
+    /// [`ListResponse`] fetches the following pages with the same filters.
+    pub async fn get_list_as<P, T>(&self, path: &str, params: &P) -> PolarResult<ListResponse<T>>
+    where
+        P: Serialize,
+        T: DeserializeOwned,
+    {
+        self.get_list(path, params).await
+    }
+
+    #[doc(hidden)]
     pub async fn delete<T>(&self, path: &str) -> PolarResult<T>
     where
         T: DeserializeOwned,
     {
-        let response = reqwest::Client::new()
-            .delete(self.base_url.join(path)?)
-            .bearer_auth(&self.access_token)
-            .send()
-            .await?;
+        let request = self.client.delete(self.url(path)?).bearer_auth(&self.access_token);
+
+        let response = self.send(request).await?;
 
         match response.status() {
-            StatusCode::OK => Ok(response.json().await.unwrap()),
+            StatusCode::OK => self.decode_body(response.bytes().await?.into()),
+            StatusCode::NO_CONTENT => json::from_empty(),
             StatusCode::NOT_FOUND => Err(PolarError::NotFound),
             StatusCode::UNPROCESSABLE_ENTITY => Err(PolarError::Validation(response.text().await?)),
             StatusCode::UNAUTHORIZED => Err(PolarError::Unauthorized),
@@ -108,6 +420,7 @@ impl Polar {
         }
     }
 
+    #[doc(hidden)]
     pub async fn get<T>(&self, path: &str) -> PolarResult<T>
     where
         T: DeserializeOwned,
@@ -115,27 +428,60 @@ impl Polar {
         self.get_with_params(path, &()).await
     }
 
+    #[doc(hidden)]
     pub async fn get_with_params<P, T>(&self, path: &str, params: &P) -> PolarResult<T>
     where
         P: Serialize,
         T: DeserializeOwned,
     {
-        let mut url = self.base_url.join(path)?;
+        let request = self
+            .client
+            .get(self.url_with_params(path, params)?)
+            .bearer_auth(&self.access_token);
 
-        if let Ok(query) = serde_qs::to_string(params) {
-            let query = REGEX_QUERY_ARRAY.replace_all(&query, "");
+        let response = self.send(request).await?;
 
-            url.set_query(Some(&query));
+        match response.status() {
+            StatusCode::OK => self.decode_body(response.bytes().await?.into()),
+            StatusCode::NOT_FOUND => Err(PolarError::NotFound),
+            StatusCode::UNPROCESSABLE_ENTITY => Err(PolarError::Validation(response.text().await?)),
+            StatusCode::UNAUTHORIZED => Err(PolarError::Unauthorized),
+            _ => Err(PolarError::Unknown(response.text().await?)),
         }
+    }
 
-        let response = reqwest::Client::new()
-            .get(url)
-            .bearer_auth(&self.access_token)
-            .send()
-            .await?;
+    /// Same as [`Polar::get_with_params`], but returns a [`ListResponse`] able to fetch the next page.
+    #[doc(hidden)]
+    pub async fn get_list<P, T>(&self, path: &str, params: &P) -> PolarResult<ListResponse<T>>
+    where
+        P: Serialize,
+        T: DeserializeOwned,
+    {
+        self.get_list_with_map(path, list::params_to_map(params)?).await
+    }
+
+    pub(crate) async fn get_list_with_map<T>(
+        &self,
+        path: &str,
+        params: serde_json::Map<String, Value>,
+    ) -> PolarResult<ListResponse<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let request = self
+            .client
+            .get(self.url_with_params(path, &params)?)
+            .bearer_auth(&self.access_token);
+
+        let response = self.send(request).await?;
 
         match response.status() {
-            StatusCode::OK => Ok(response.json().await.unwrap()),
+            StatusCode::OK => {
+                let rate_limit = RateLimit::from_headers(response.headers());
+                let page = self.decode_body(response.bytes().await?.into())?;
+
+                Ok(ListResponse::new(page, rate_limit, path.to_owned(), params))
+            }
             StatusCode::NOT_FOUND => Err(PolarError::NotFound),
             StatusCode::UNPROCESSABLE_ENTITY => Err(PolarError::Validation(response.text().await?)),
             StatusCode::UNAUTHORIZED => Err(PolarError::Unauthorized),
@@ -143,20 +489,23 @@ impl Polar {
         }
     }
 
+    #[doc(hidden)]
     pub async fn patch<P, T>(&self, path: &str, params: &P) -> PolarResult<T>
     where
         P: Serialize,
         T: DeserializeOwned,
     {
-        let response = reqwest::Client::new()
-            .patch(self.base_url.join(path)?)
+        let request = self
+            .client
+            .patch(self.url(path)?)
             .bearer_auth(&self.access_token)
-            .json(params)
-            .send()
-            .await?;
+            .header(CONTENT_TYPE, "application/json")
+            .body(Json::to_vec(params)?);
+
+        let response = self.send(request).await?;
 
         match response.status() {
-            StatusCode::OK => Ok(response.json().await.unwrap()),
+            StatusCode::OK => self.decode_body(response.bytes().await?.into()),
             StatusCode::NOT_FOUND => Err(PolarError::NotFound),
             StatusCode::UNPROCESSABLE_ENTITY => Err(PolarError::Validation(response.text().await?)),
             StatusCode::UNAUTHORIZED => Err(PolarError::Unauthorized),
@@ -164,26 +513,143 @@ impl Polar {
         }
     }
 
+    #[doc(hidden)]
     pub async fn post<P, T>(&self, path: &str, params: &P) -> PolarResult<T>
     where
         P: Serialize,
         T: DeserializeOwned,
     {
-        let response = reqwest::Client::new()
-            .post(self.base_url.join(path)?)
+        let request = self
+            .client
+            .post(self.url(path)?)
             .bearer_auth(&self.access_token)
-            .json(params)
-            .send()
-            .await?;
+            .header(CONTENT_TYPE, "application/json")
+            .body(Json::to_vec(params)?);
+
+        let response = self.send(request).await?;
+
+        self.decode_post_response(response).await
+    }
+
+    /// Same as [`Polar::post`], but compresses the body with gzip when it's larger than the threshold set with
+    /// [`Polar::with_event_compression`].
+    #[cfg(feature = "gzip")]
+    async fn post_compressed<P, T>(&self, path: &str, params: &P) -> PolarResult<T>
+    where
+        P: Serialize,
+        T: DeserializeOwned,
+    {
+        use std::io::Write;
+
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+        use reqwest::header::CONTENT_ENCODING;
+
+        let body = Json::to_vec(params)?;
 
+        if let Some(threshold) = self.event_compression_threshold
+            && body.len() > threshold
+        {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+
+            encoder.write_all(&body)?;
+
+            let request = self
+                .client
+                .post(self.url(path)?)
+                .bearer_auth(&self.access_token)
+                .header(CONTENT_TYPE, "application/json")
+                .header(CONTENT_ENCODING, "gzip")
+                .body(encoder.finish()?);
+
+            let response = self.send(request).await?;
+
+            if response.status() != StatusCode::UNSUPPORTED_MEDIA_TYPE {
+                return self.decode_post_response(response).await;
+            }
+        }
+
+        let request = self
+            .client
+            .post(self.url(path)?)
+            .bearer_auth(&self.access_token)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body);
+
+        let response = self.send(request).await?;
+
+        self.decode_post_response(response).await
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder) -> PolarResult<reqwest::Response> {
+        let mut request = request.build()?;
+
+        if let Some(hook) = &self.request_hook {
+            hook(&mut request);
+        }
+
+        let method = request.method().clone();
+        let response = self.client.execute(request).await?;
+
+        if let Some(hook) = &self.deprecation_hook
+            && let Some(deprecation) = Deprecation::from_response(method, &response)
+        {
+            hook(&deprecation);
+        }
+
+        Ok(response)
+    }
+
+    async fn decode_post_response<T: DeserializeOwned>(&self, response: reqwest::Response) -> PolarResult<T> {
         match response.status() {
-            StatusCode::CREATED => Ok(response.json().await.unwrap()),
+            StatusCode::OK | StatusCode::CREATED | StatusCode::ACCEPTED => {
+                self.decode_body(response.bytes().await?.into())
+            }
             StatusCode::UNPROCESSABLE_ENTITY => Err(PolarError::Validation(response.text().await?)),
             StatusCode::UNAUTHORIZED => Err(PolarError::Unauthorized),
             _ => Err(PolarError::Unknown(response.text().await?)),
         }
     }
 
+    fn decode_body<T: DeserializeOwned>(&self, bytes: Vec<u8>) -> PolarResult<T> {
+        match &self.unknown_field_hook {
+            Some(hook) => json::from_body(bytes, Some(hook.as_ref())),
+            None => json::from_body(bytes, None),
+        }
+    }
+
+    fn url(&self, path: &str) -> PolarResult<reqwest::Url> {
+        let url = self.base_url.join(path)?;
+
+        if let Some(allowed_hosts) = &self.allowed_hosts {
+            let is_allowed_host = url
+                .host_str()
+                .is_some_and(|host| allowed_hosts.iter().any(|allowed| allowed.eq_ignore_ascii_case(host)));
+
+            if !is_allowed_host
+                || url.scheme() != self.base_url.scheme()
+                || url.port_or_known_default() != self.base_url.port_or_known_default()
+                || !url.path().starts_with(self.base_url.path())
+            {
+                return Err(PolarError::Request(format!("{url} is not allowed")));
+            }
+        }
+
+        Ok(url)
+    }
+
+    fn url_with_params<P: Serialize>(&self, path: &str, params: &P) -> PolarResult<reqwest::Url> {
+        let mut url = self.url(path)?;
+
+        if let Ok(query) = serde_qs::to_string(params) {
+            let query = REGEX_QUERY_ARRAY.replace_all(&query, "");
+
+            url.set_query(Some(&query));
+        }
+
+        Ok(url)
+    }
+
     /// **Create a checkout session.**
     ///
     /// Scopes: `checkouts:write`
@@ -210,261 +676,3032 @@ impl Polar {
     pub async fn list_checkout_sessions(
         &self,
         params: &ListCheckoutSessionsParams,
-    ) -> PolarResult<Page<CheckoutSession>> {
-        self.get_with_params("checkouts", params).await
+    ) -> PolarResult<ListResponse<CheckoutSession>> {
+        self.get_list("checkouts", params).await
     }
 
-    /// **Get a subscription by ID.**
+    /// **Get a checkout link by ID.**
     ///
-    /// Scopes: `subscriptions:read` `subscriptions:write`
+    /// Scopes: `checkout_links:read` `checkout_links:write`
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/subscriptions/get>
-    pub async fn get_subscription(&self, id: Uuid) -> PolarResult<Subscription> {
-        self.get(&format!("subscriptions/{id}")).await
+    /// Reference: <https://docs.polar.sh/api-reference/checkout-links/get>
+    pub async fn get_checkout_link(&self, id: Uuid) -> PolarResult<CheckoutLink> {
+        self.get(&format!("checkout-links/{id}")).await
     }
 
-    /// **List subscriptions.**
+    /// **Update a checkout link.**
     ///
-    /// Scopes: `subscriptions:read` `subscriptions:write`
+    /// Scopes: `checkout_links:write`
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/subscriptions/list>
-    pub async fn list_subscriptions(&self, params: &ListSubscriptionsParams) -> PolarResult<Page<Subscription>> {
-        self.get_with_params("subscriptions", params).await
+    /// Reference: <https://docs.polar.sh/api-reference/checkout-links/update>
+    pub async fn update_checkout_link(&self, id: Uuid, params: &UpdateCheckoutLinkParams) -> PolarResult<CheckoutLink> {
+        self.patch(&format!("checkout-links/{id}"), params).await
     }
 
-    /// **Update a subscription.**
+    /// **Delete a checkout link.**
     ///
-    /// Scopes: `subscriptions:write`
+    /// Scopes: `checkout_links:write`
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/subscriptions/update>
-    pub async fn update_subscription(&self, id: Uuid, params: &SubscriptionParams) -> PolarResult<Subscription> {
-        self.patch(&format!("subscriptions/{id}"), params).await
+    /// Reference: <https://docs.polar.sh/api-reference/checkout-links/delete>
+    pub async fn delete_checkout_link(&self, id: Uuid) -> PolarResult<()> {
+        self.delete(&format!("checkout-links/{id}")).await
     }
 
-    /// **Revoke a subscription, i.e cancel immediately.**
+    /// **Create a customer.**
     ///
-    /// Scopes: `subscriptions:write`
+    /// Scopes: `customers:write`
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/subscriptions/revoke>
-    pub async fn revoke_subscription(&self, id: Uuid) -> PolarResult<Subscription> {
-        self.delete(&format!("subscriptions/{id}")).await
+    /// Reference: <https://docs.polar.sh/api-reference/customers/create>
+    pub async fn create_customer(&self, params: &CustomerParams) -> PolarResult<Customer> {
+        self.post("customers", params).await
     }
 
-    /// **Get a product by ID.**
+    /// **List customers.**
     ///
-    /// Scopes: `products:read` `products:write`
+    /// Scopes: `customers:read` `customers:write`
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/products/get>
-    pub async fn get_product(&self, id: Uuid) -> PolarResult<Product> {
-        self.get(&format!("products/{id}")).await
+    /// Reference: <https://docs.polar.sh/api-reference/customers/list>
+    pub async fn list_customers(&self, params: &ListCustomersParams) -> PolarResult<ListResponse<Customer>> {
+        self.get_list("customers", params).await
     }
 
-    /// **List products.**
+    /// **Get a customer by ID.**
     ///
-    /// Scopes: `products:read` `products:write`
+    /// Scopes: `customers:read` `customers:write`
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/products/list>
-    pub async fn list_products(&self, params: &ListProductsParams) -> PolarResult<Page<Product>> {
-        self.get_with_params("products", params).await
+    /// Reference: <https://docs.polar.sh/api-reference/customers/get>
+    pub async fn get_customer(&self, id: Uuid) -> PolarResult<Customer> {
+        self.get(&format!("customers/{id}")).await
     }
 
-    /// **Create a product.**
+    /// **Update a customer.**
     ///
-    /// Scopes: `products:write`
+    /// Scopes: `customers:write`
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/products/create>
-    pub async fn create_product(&self, params: &ProductParams) -> PolarResult<Product> {
-        self.post("products", params).await
+    /// Reference: <https://docs.polar.sh/api-reference/customers/update>
+    pub async fn update_customer(&self, id: Uuid, params: &UpdateCustomerParams) -> PolarResult<Customer> {
+        self.patch(&format!("customers/{id}"), params).await
     }
 
-    /// **Update a product.**
+    /// **Delete a customer.**
     ///
-    /// Scopes: `products:write`
+    /// Immediately cancels any active subscriptions and revokes any active benefits.
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/products/update>
-    pub async fn update_product(&self, id: Uuid, params: &UpdateProductParams) -> PolarResult<Product> {
-        self.patch(&format!("products/{id}"), params).await
+    /// Scopes: `customers:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/customers/delete>
+    pub async fn delete_customer(&self, id: Uuid) -> PolarResult<()> {
+        self.delete(&format!("customers/{id}")).await
     }
 
-    /// **Update benefits granted by a product..**
+    /// **Get a customer by external ID.**
     ///
-    /// Scopes: `products:write`
+    /// Scopes: `customers:read` `customers:write`
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/products/update-benefits>
-    pub async fn update_product_benefits(&self, id: Uuid, benefits: Vec<Uuid>) -> PolarResult<Product> {
-        self.patch(&format!("products/{id}/benefits"), &json!({ "benefits": benefits }))
-            .await
+    /// Reference: <https://docs.polar.sh/api-reference/customers/get-external>
+    pub async fn get_customer_by_external_id(&self, external_id: &str) -> PolarResult<Customer> {
+        self.get(&customer_external_path(external_id)?).await
     }
 
-    /// **Ingest batch of events.**
+    /// **Update a customer by external ID.**
     ///
-    /// Scopes: `events:write`
+    /// Scopes: `customers:write`
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/events/ingest>
-    pub async fn ingest_events(&self, events: Vec<EventParams>) -> PolarResult<i64> {
-        self.post("events/ingest", &json!({ "events": events }))
-            .await
-            .map(|resp: Value| resp["inserted"].as_i64().unwrap())
-    }
-
-    // **Get an event by ID.**
-    //
-    // Scopes: `events:read` `events:write`
-    //
-    // Reference: <https://docs.polar.sh/api-reference/events/get>
-    pub async fn get_event(&self, id: Uuid) -> PolarResult<Event> {
-        self.get(&format!("events/{id}")).await
+    /// Reference: <https://docs.polar.sh/api-reference/customers/update-external>
+    pub async fn update_customer_by_external_id(
+        &self,
+        external_id: &str,
+        params: &UpdateCustomerParams,
+    ) -> PolarResult<Customer> {
+        self.patch(&customer_external_path(external_id)?, params).await
     }
 
-    /// **Create a meter.**
+    /// **Delete a customer by external ID.**
     ///
-    /// Scopes: `meters:write`
+    /// Immediately cancels any active subscriptions and revokes any active benefits.
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/meters/create>
-    pub async fn create_meter(&self, params: &MeterParams) -> PolarResult<Meter> {
-        self.post("meters", params).await
+    /// Scopes: `customers:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/customers/delete-external>
+    pub async fn delete_customer_by_external_id(&self, external_id: &str) -> PolarResult<()> {
+        self.delete(&customer_external_path(external_id)?).await
     }
 
-    /// **Get a meter by ID.**
-    ///
-    /// Scopes: `meters:read` `meters:write`
+    /// Updates the name and email of a customer, addressed by external ID, only when they differ from the ones known
+    /// by Polar. Returns whether the customer was updated.
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/meters/get>
-    pub async fn get_meter(&self, id: Uuid) -> PolarResult<Meter> {
-        self.get(&format!("meters/{id}")).await
+    /// Meant for profile update hooks that fire often, to avoid a write on every call. Emails are compared ignoring
+    /// ASCII case.
+    pub async fn sync_customer_identity(
+        &self,
+        external_id: &str,
+        name: Option<&str>,
+        email: &str,
+    ) -> PolarResult<bool> {
+        let customer = self.get_customer_by_external_id(external_id).await?;
+        let params = UpdateCustomerParams {
+            email: (!customer.email.eq_ignore_ascii_case(email)).then(|| email.to_owned()),
+            name: (customer.name.as_deref() != name).then(|| name.map(str::to_owned)),
+            ..Default::default()
+        };
+
+        if params.email.is_none() && params.name.is_none() {
+            return Ok(false);
+        }
+
+        self.update_customer_by_external_id(external_id, &params).await?;
+
+        Ok(true)
     }
 
-    /// **List meters.**
+    /// **Get a customer state by external ID.**
     ///
-    /// Scopes: `meters:read` `meters:write`
+    /// Scopes: `customers:read` `customers:write`
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/meters/list>
-    pub async fn list_meters(&self, params: &ListMetersParams) -> PolarResult<Page<Meter>> {
-        self.get_with_params("meters", params).await
+    /// Reference: <https://docs.polar.sh/api-reference/customers/state-external>
+    pub async fn get_customer_state_by_external_id(&self, external_id: &str) -> PolarResult<CustomerState> {
+        self.get(&format!("{}/state", customer_external_path(external_id)?))
+            .await
     }
 
-    /// **Update a meter.**
-    ///
-    /// Scopes: `meters:write`
+    /// Whether a customer, addressed by external ID, is currently granted a benefit. Customers unknown to Polar have
+    /// no benefit.
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/meters/update>
-    pub async fn update_meter(&self, id: Uuid, params: &UpdateMeterParams) -> PolarResult<Meter> {
-        self.patch(&format!("meters/{id}"), params).await
+    /// The customer state is taken from the cache set with [`Polar::with_customer_state_cache`], if any.
+    pub async fn customer_has_benefit(&self, external_customer_id: &str, benefit_id: Uuid) -> PolarResult<bool> {
+        Ok(self.customer_state(external_customer_id).await?.is_some_and(|state| {
+            state
+                .granted_benefits
+                .iter()
+                .any(|grant| grant.benefit_id == benefit_id)
+        }))
     }
 
-    /// **Get quantities of a meter over a time period.**
-    ///
-    /// Scopes: `meters:read` `meters:write`
+    /// Whether a customer, addressed by external ID, has an active subscription to a product. Customers unknown to
+    /// Polar have none.
     ///
-    /// Reference: <https://docs.polar.sh/api-reference/meters/get-quantities>
-    pub async fn get_meter_quantities(&self, id: Uuid, params: &MeterQuantitiesParams) -> PolarResult<MeterQuantities> {
-        self.get_with_params(&format!("meters/{id}/quantities"), params).await
+    /// One-time purchases are not part of the customer state, see [`quickstart::customer_has_access`] for them. The
+    /// customer state is taken from the cache set with [`Polar::with_customer_state_cache`], if any.
+    pub async fn customer_has_product(&self, external_customer_id: &str, product_id: Uuid) -> PolarResult<bool> {
+        Ok(self.customer_state(external_customer_id).await?.is_some_and(|state| {
+            state
+                .active_subscriptions
+                .iter()
+                .any(|subscription| subscription.product_id == product_id)
+        }))
+    }
+
+    async fn customer_state(&self, external_id: &str) -> PolarResult<Option<Arc<CustomerState>>> {
+        if let Some(state) = self
+            .customer_state_cache
+            .as_ref()
+            .and_then(|cache| cache.get(external_id))
+        {
+            return Ok(Some(state));
+        }
+
+        let state = match self.get_customer_state_by_external_id(external_id).await {
+            Ok(state) => Arc::new(state),
+            Err(PolarError::NotFound) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        if let Some(cache) = &self.customer_state_cache {
+            cache.insert(external_id, state.clone());
+        }
+
+        Ok(Some(state))
+    }
+
+    /// **List orders.**
+    ///
+    /// Scopes: `orders:read`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/orders/list>
+    pub async fn list_orders(&self, params: &ListOrdersParams) -> PolarResult<ListResponse<Order>> {
+        self.get_list("orders", params).await
+    }
+
+    /// **Get an order by ID.**
+    ///
+    /// Scopes: `orders:read`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/orders/get>
+    pub async fn get_order(&self, id: Uuid) -> PolarResult<Order> {
+        self.get(&format!("orders/{id}")).await
+    }
+
+    /// **Update an order.**
+    ///
+    /// Scopes: `orders:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/orders/update>
+    pub async fn update_order(&self, id: Uuid, params: &UpdateOrderParams) -> PolarResult<Order> {
+        self.patch(&format!("orders/{id}"), params).await
+    }
+
+    /// **Trigger generation of an order's invoice.**
+    ///
+    /// The invoice is generated asynchronously, use [`Polar::get_order_invoice`] to fetch it once ready.
+    ///
+    /// Scopes: `orders:read`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/orders/post-invoice>
+    pub async fn generate_order_invoice(&self, id: Uuid) -> PolarResult<()> {
+        self.post(&format!("orders/{id}/invoice"), &json!({}))
+            .await
+            .map(|_: Value| ())
+    }
+
+    /// **Trigger a payment retry for an order with a failed payment.**
+    ///
+    /// The payment is retried asynchronously, the order is updated once it's processed.
+    ///
+    /// Scopes: `orders:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/orders/retry-payment>
+    pub async fn retry_order_payment(&self, id: Uuid) -> PolarResult<()> {
+        self.post(&format!("orders/{id}/retry-payment"), &json!({}))
+            .await
+            .map(|_: Value| ())
+    }
+
+    /// **Get an order's invoice data.**
+    ///
+    /// Scopes: `orders:read`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/orders/get-invoice>
+    pub async fn get_order_invoice(&self, id: Uuid) -> PolarResult<OrderInvoice> {
+        self.get(&format!("orders/{id}/invoice")).await
+    }
+
+    /// **Get a subscription by ID.**
+    ///
+    /// Scopes: `subscriptions:read` `subscriptions:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/subscriptions/get>
+    pub async fn get_subscription(&self, id: Uuid) -> PolarResult<Subscription> {
+        self.get(&format!("subscriptions/{id}")).await
+    }
+
+    /// **List payments.**
+    ///
+    /// Scopes: `payments:read`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/payments/list>
+    pub async fn list_payments(&self, params: &ListPaymentsParams) -> PolarResult<ListResponse<Payment>> {
+        self.get_list("payments", params).await
+    }
+
+    /// **Get a payment by ID.**
+    ///
+    /// Scopes: `payments:read`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/payments/get>
+    pub async fn get_payment(&self, id: Uuid) -> PolarResult<Payment> {
+        self.get(&format!("payments/{id}")).await
+    }
+
+    /// **List subscriptions.**
+    ///
+    /// Scopes: `subscriptions:read` `subscriptions:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/subscriptions/list>
+    pub async fn list_subscriptions(
+        &self,
+        params: &ListSubscriptionsParams,
+    ) -> PolarResult<ListResponse<Subscription>> {
+        self.get_list("subscriptions", params).await
+    }
+
+    /// **Update a subscription.**
+    ///
+    /// Scopes: `subscriptions:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/subscriptions/update>
+    pub async fn update_subscription(&self, id: Uuid, params: &SubscriptionParams) -> PolarResult<Subscription> {
+        self.patch(&format!("subscriptions/{id}"), params).await
+    }
+
+    /// **Revoke a subscription, i.e cancel immediately.**
+    ///
+    /// Scopes: `subscriptions:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/subscriptions/revoke>
+    pub async fn revoke_subscription(&self, id: Uuid) -> PolarResult<Subscription> {
+        self.delete(&format!("subscriptions/{id}")).await
+    }
+
+    /// **Get a product by ID.**
+    ///
+    /// Scopes: `products:read` `products:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/products/get>
+    pub async fn get_product(&self, id: Uuid) -> PolarResult<Product> {
+        self.get(&format!("products/{id}")).await
+    }
+
+    /// **List products.**
+    ///
+    /// Scopes: `products:read` `products:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/products/list>
+    pub async fn list_products(&self, params: &ListProductsParams) -> PolarResult<ListResponse<Product>> {
+        self.get_list("products", params).await
+    }
+
+    /// **List products, only if one of them was modified after the given date.**
+    ///
+    /// The API doesn't support conditional requests, so the page is fetched and its products compared client-side.
+    /// Returns `None` when none was created or modified after `since`, so the page fetched by a previous sync can be
+    /// reused. Products leaving the page, e.g. archived ones when listing unarchived products only, are not detected.
+    ///
+    /// Scopes: `products:read` `products:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/products/list>
+    pub async fn list_products_if_modified_since(
+        &self,
+        params: &ListProductsParams,
+        since: DateTime<Utc>,
+    ) -> PolarResult<Option<ListResponse<Product>>> {
+        let products = self.list_products(params).await?;
+
+        Ok(products.modified_since(since, |product| product.modified_at.unwrap_or(product.created_at)))
+    }
+
+    /// **Create a product.**
+    ///
+    /// Scopes: `products:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/products/create>
+    pub async fn create_product(&self, params: &ProductParams) -> PolarResult<Product> {
+        self.post("products", params).await
+    }
+
+    /// **Update a product.**
+    ///
+    /// Scopes: `products:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/products/update>
+    pub async fn update_product(&self, id: Uuid, params: &UpdateProductParams) -> PolarResult<Product> {
+        self.patch(&format!("products/{id}"), params).await
+    }
+
+    /// **Update benefits granted by a product.**
+    ///
+    /// Scopes: `products:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/products/update-benefits>
+    pub async fn update_product_benefits(
+        &self,
+        id: Uuid,
+        params: &UpdateProductBenefitsParams,
+    ) -> PolarResult<Product> {
+        self.post(&format!("products/{id}/benefits"), params).await
+    }
+
+    /// **Create a benefit.**
+    ///
+    /// Scopes: `benefits:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/benefits/create>
+    pub async fn create_benefit(&self, params: &BenefitCreateParams) -> PolarResult<Benefit> {
+        self.post("benefits", params).await
+    }
+
+    /// **List benefits.**
+    ///
+    /// Scopes: `benefits:read` `benefits:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/benefits/list>
+    pub async fn list_benefits(&self, params: &ListBenefitsParams) -> PolarResult<ListResponse<Benefit>> {
+        self.get_list("benefits", params).await
+    }
+
+    /// **List benefits, only if one of them was modified after the given date.**
+    ///
+    /// Same as [`Polar::list_products_if_modified_since`], for benefits.
+    ///
+    /// Scopes: `benefits:read` `benefits:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/benefits/list>
+    pub async fn list_benefits_if_modified_since(
+        &self,
+        params: &ListBenefitsParams,
+        since: DateTime<Utc>,
+    ) -> PolarResult<Option<ListResponse<Benefit>>> {
+        let benefits = self.list_benefits(params).await?;
+
+        Ok(benefits.modified_since(since, |benefit| benefit.modified_at.unwrap_or(benefit.created_at)))
+    }
+
+    /// **Get a benefit by ID.**
+    ///
+    /// Scopes: `benefits:read` `benefits:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/benefits/get>
+    pub async fn get_benefit(&self, id: Uuid) -> PolarResult<Benefit> {
+        self.get(&format!("benefits/{id}")).await
+    }
+
+    /// **Update a benefit.**
+    ///
+    /// Scopes: `benefits:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/benefits/update>
+    pub async fn update_benefit(&self, id: Uuid, params: &UpdateBenefitParams) -> PolarResult<Benefit> {
+        self.patch(&format!("benefits/{id}"), params).await
+    }
+
+    /// **Delete a benefit.**
+    ///
+    /// Revokes the benefit from all customers having it granted.
+    ///
+    /// Scopes: `benefits:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/benefits/delete>
+    pub async fn delete_benefit(&self, id: Uuid) -> PolarResult<()> {
+        self.delete(&format!("benefits/{id}")).await
+    }
+
+    /// **Create a discount.**
+    ///
+    /// Scopes: `discounts:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/discounts/create>
+    pub async fn create_discount(&self, params: &DiscountParams) -> PolarResult<Discount> {
+        self.post("discounts", params).await
+    }
+
+    /// **List discounts.**
+    ///
+    /// Scopes: `discounts:read` `discounts:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/discounts/list>
+    pub async fn list_discounts(&self, params: &ListDiscountsParams) -> PolarResult<ListResponse<Discount>> {
+        self.get_list("discounts", params).await
+    }
+
+    /// **Get a discount by ID.**
+    ///
+    /// Scopes: `discounts:read` `discounts:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/discounts/get>
+    pub async fn get_discount(&self, id: Uuid) -> PolarResult<Discount> {
+        self.get(&format!("discounts/{id}")).await
+    }
+
+    /// **Update a discount.**
+    ///
+    /// Scopes: `discounts:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/discounts/update>
+    pub async fn update_discount(&self, id: Uuid, params: &UpdateDiscountParams) -> PolarResult<Discount> {
+        self.patch(&format!("discounts/{id}"), params).await
+    }
+
+    /// **Delete a discount.**
+    ///
+    /// Scopes: `discounts:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/discounts/delete>
+    pub async fn delete_discount(&self, id: Uuid) -> PolarResult<()> {
+        self.delete(&format!("discounts/{id}")).await
+    }
+
+    /// **List license keys.**
+    ///
+    /// Scopes: `license_keys:read` `license_keys:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/license-keys/list>
+    pub async fn list_license_keys(&self, params: &ListLicenseKeysParams) -> PolarResult<ListResponse<LicenseKey>> {
+        self.get_list("license-keys", params).await
+    }
+
+    /// **Get a license key by ID, with its activations.**
+    ///
+    /// Scopes: `license_keys:read` `license_keys:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/license-keys/get>
+    pub async fn get_license_key(&self, id: Uuid) -> PolarResult<LicenseKey> {
+        self.get(&format!("license-keys/{id}")).await
+    }
+
+    /// **Update a license key.**
+    ///
+    /// Scopes: `license_keys:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/license-keys/update>
+    pub async fn update_license_key(&self, id: Uuid, params: &UpdateLicenseKeyParams) -> PolarResult<LicenseKey> {
+        self.patch(&format!("license-keys/{id}"), params).await
+    }
+
+    /// **Get a license key activation by ID.**
+    ///
+    /// Scopes: `license_keys:read` `license_keys:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/license-keys/get-activation>
+    pub async fn get_license_key_activation(
+        &self,
+        license_key_id: Uuid,
+        activation_id: Uuid,
+    ) -> PolarResult<LicenseKeyActivation> {
+        self.get(&format!("license-keys/{license_key_id}/activations/{activation_id}"))
+            .await
+    }
+
+    /// **List files.**
+    ///
+    /// Scopes: `files:read` `files:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/files/list>
+    pub async fn list_files(&self, params: &ListFilesParams) -> PolarResult<ListResponse<FileRead>> {
+        self.get_list("files", params).await
+    }
+
+    /// **Create a file**, to be uploaded in parts to the presigned URLs of the returned [`FileUpload`].
+    ///
+    /// Scopes: `files:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/files/create>
+    pub async fn create_file(&self, params: &FileCreateParams) -> PolarResult<FileUpload> {
+        self.post("files", params).await
+    }
+
+    /// **Complete a file upload**, once every part has been uploaded to its presigned URL.
+    ///
+    /// Scopes: `files:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/files/uploaded>
+    pub async fn complete_file_upload(&self, id: Uuid, params: &FileUploadCompletedParams) -> PolarResult<FileRead> {
+        self.post(&format!("files/{id}/uploaded"), params).await
+    }
+
+    /// **Update a file.**
+    ///
+    /// Scopes: `files:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/files/update>
+    pub async fn update_file(&self, id: Uuid, params: &UpdateFileParams) -> PolarResult<FileRead> {
+        self.patch(&format!("files/{id}"), params).await
+    }
+
+    /// **Delete a file.**
+    ///
+    /// Scopes: `files:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/files/delete>
+    pub async fn delete_file(&self, id: Uuid) -> PolarResult<()> {
+        self.delete(&format!("files/{id}")).await
+    }
+
+    /// **Create a custom field.**
+    ///
+    /// Scopes: `custom_fields:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/custom-fields/create>
+    pub async fn create_custom_field(&self, params: &CustomFieldParams) -> PolarResult<CustomField> {
+        self.post("custom-fields", params).await
+    }
+
+    /// **Update a custom field.**
+    ///
+    /// Scopes: `custom_fields:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/custom-fields/update>
+    pub async fn update_custom_field(&self, id: Uuid, params: &UpdateCustomFieldParams) -> PolarResult<CustomField> {
+        self.patch(&format!("custom-fields/{id}"), params).await
+    }
+
+    /// **Delete a custom field.**
+    ///
+    /// Scopes: `custom_fields:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/custom-fields/delete>
+    pub async fn delete_custom_field(&self, id: Uuid) -> PolarResult<()> {
+        self.delete(&format!("custom-fields/{id}")).await
+    }
+
+    /// **Ingest batch of events.**
+    ///
+    /// Scopes: `events:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/events/ingest>
+    pub async fn ingest_events(&self, events: &[EventParams<'_>]) -> PolarResult<i64> {
+        // Serialized straight from the borrowed events, unlike `json!` which would copy every string.
+        #[derive(Serialize)]
+        struct IngestEvents<'a> {
+            events: &'a [EventParams<'a>],
+        }
+
+        #[cfg(feature = "gzip")]
+        let result = self.post_compressed("events/ingest", &IngestEvents { events }).await;
+        #[cfg(not(feature = "gzip"))]
+        let result = self.post("events/ingest", &IngestEvents { events }).await;
+
+        result.and_then(|resp: Value| {
+            resp["inserted"]
+                .as_i64()
+                .ok_or_else(|| PolarError::Unknown(format!("unexpected response: {resp}")))
+        })
+    }
+
+    // **Get an event by ID.**
+    //
+    // Scopes: `events:read` `events:write`
+    //
+    // Reference: <https://docs.polar.sh/api-reference/events/get>
+    pub async fn get_event(&self, id: Uuid) -> PolarResult<Event> {
+        self.get(&format!("events/{id}")).await
+    }
+
+    /// **Create a meter.**
+    ///
+    /// Scopes: `meters:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/meters/create>
+    pub async fn create_meter(&self, params: &MeterParams) -> PolarResult<Meter> {
+        self.post("meters", params).await
+    }
+
+    /// **Get a meter by ID.**
+    ///
+    /// Scopes: `meters:read` `meters:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/meters/get>
+    pub async fn get_meter(&self, id: Uuid) -> PolarResult<Meter> {
+        self.get(&format!("meters/{id}")).await
+    }
+
+    /// **List meters.**
+    ///
+    /// Scopes: `meters:read` `meters:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/meters/list>
+    pub async fn list_meters(&self, params: &ListMetersParams) -> PolarResult<ListResponse<Meter>> {
+        self.get_list("meters", params).await
+    }
+
+    /// **Update a meter.**
+    ///
+    /// Scopes: `meters:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/meters/update>
+    pub async fn update_meter(&self, id: Uuid, params: &UpdateMeterParams) -> PolarResult<Meter> {
+        self.patch(&format!("meters/{id}"), params).await
+    }
+
+    /// **Archive a meter.**
+    ///
+    /// Archived meters are no longer used for billing. Use [`Polar::update_meter`] with `is_archived: Some(false)` to
+    /// unarchive it.
+    ///
+    /// Scopes: `meters:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/meters/update>
+    pub async fn archive_meter(&self, id: Uuid) -> PolarResult<Meter> {
+        self.patch(&format!("meters/{id}"), &json!({ "is_archived": true }))
+            .await
+    }
+
+    /// **Get quantities of a meter over a time period.**
+    ///
+    /// Scopes: `meters:read` `meters:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/meters/get-quantities>
+    pub async fn get_meter_quantities(&self, id: Uuid, params: &MeterQuantitiesParams) -> PolarResult<MeterQuantities> {
+        self.get_with_params(&format!("meters/{id}/quantities"), params).await
+    }
+
+    /// **List webhook endpoints.**
+    ///
+    /// Scopes: `webhooks:read` `webhooks:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/webhooks/endpoints/list>
+    pub async fn list_webhook_endpoints(
+        &self,
+        params: &ListWebhookEndpointsParams,
+    ) -> PolarResult<ListResponse<WebhookEndpoint>> {
+        self.get_list("webhooks/endpoints", params).await
+    }
+
+    /// **Create a webhook endpoint.**
+    ///
+    /// Scopes: `webhooks:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/webhooks/endpoints/create>
+    pub async fn create_webhook_endpoint(&self, params: &WebhookEndpointParams) -> PolarResult<WebhookEndpoint> {
+        self.post("webhooks/endpoints", params).await
+    }
+
+    /// **Update a webhook endpoint.**
+    ///
+    /// Scopes: `webhooks:write`
+    ///
+    /// Reference: <https://docs.polar.sh/api-reference/webhooks/endpoints/update>
+    pub async fn update_webhook_endpoint(
+        &self,
+        id: Uuid,
+        params: &UpdateWebhookEndpointParams,
+    ) -> PolarResult<WebhookEndpoint> {
+        self.patch(&format!("webhooks/endpoints/{id}"), params).await
+    }
+
+    /// Makes sure a webhook endpoint sends exactly the given events to the given URL.
+    ///
+    /// The endpoint with the same URL is updated if its events differ, or created with the `raw` format if there is
+    /// none, so calling it again with the same arguments doesn't change anything.
+    pub async fn ensure_webhook(&self, url: &Url, events: &[WebhookEventType]) -> PolarResult<WebhookEndpoint> {
+        let params = ListWebhookEndpointsParams {
+            limit: Some(100),
+            ..Default::default()
+        };
+        let endpoint = self
+            .list_webhook_endpoints(&params)
+            .await?
+            .find_item(self, |endpoint| &endpoint.url == url)
+            .await?;
+
+        if let Some(endpoint) = endpoint {
+            if endpoint.events_diff(events).is_empty() {
+                return Ok(endpoint);
+            }
+
+            return self
+                .update_webhook_endpoint(
+                    endpoint.id,
+                    &UpdateWebhookEndpointParams {
+                        events: Some(events.to_vec()),
+                        ..Default::default()
+                    },
+                )
+                .await;
+        }
+
+        self.create_webhook_endpoint(&WebhookEndpointParams {
+            url: url.clone(),
+            format: WebhookFormat::Raw,
+            events: events.to_vec(),
+            organization_id: None,
+        })
+        .await
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use std::{fs::File, io::BufReader};
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    use serde_json::from_value;
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    use super::*;
+    use crate::test_support::get_fixture;
+
+    async fn get_mock<B: Serialize>(method: &str, path: &str, status_code: u16, body: B) -> MockServer {
+        let mock_server = MockServer::start().await;
+
+        Mock::given(matchers::method(method))
+            .and(matchers::path(path))
+            .respond_with(ResponseTemplate::new(status_code).set_body_json(body))
+            .mount(&mock_server)
+            .await;
+
+        mock_server
+    }
+
+    fn get_test_polar(base_url: String) -> Polar {
+        Polar::new(base_url, "123").ok().unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_request_allowed_host() {
+        let mock = get_mock("GET", "/customers/me", 200, json!({})).await;
+
+        let polar = get_test_polar(mock.uri()).with_allowed_hosts(["127.0.0.1"]);
+
+        let result = polar.get::<Value>("customers/me").await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_request_host_outside_allowlist() {
+        let mock = get_mock("GET", "/customers/me", 200, json!({})).await;
+
+        let polar = get_test_polar(mock.uri()).with_allowed_hosts(["api.polar.sh"]);
+
+        let result = polar.get::<Value>("customers/me").await;
+
+        assert!(matches!(result, Err(PolarError::Request(_))));
+        assert!(mock.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_not_request_absolute_url_outside_allowlist() {
+        let mock = MockServer::start().await;
+
+        let polar = get_test_polar(format!("{}/v1/", mock.uri())).with_allowed_hosts(["127.0.0.1"]);
+
+        for path in ["https://example.com/v1/", "//example.com/v1/", "http://localhost/v1/"] {
+            let result = polar.get::<Value>(path).await;
+
+            assert!(
+                matches!(result, Err(PolarError::Request(_))),
+                "{path} should not be allowed"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn should_not_request_path_outside_base_url() {
+        let mock = get_mock("DELETE", "/admin", 204, json!({})).await;
+
+        let polar = get_test_polar(format!("{}/v1/", mock.uri())).with_allowed_hosts(["127.0.0.1"]);
+
+        for path in ["../admin", "/admin", "%2e%2e/admin"] {
+            let result = polar.delete::<()>(path).await;
+
+            assert!(
+                matches!(result, Err(PolarError::Request(_))),
+                "{path} should not be allowed"
+            );
+        }
+
+        assert!(mock.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_not_request_other_port_of_allowed_host() {
+        let mock = get_mock("GET", "/customers/me", 200, json!({})).await;
+        let other_mock = MockServer::start().await;
+
+        let polar = get_test_polar(mock.uri()).with_allowed_hosts(["127.0.0.1"]);
+
+        let result = polar.get::<Value>(&format!("{}/customers/me", other_mock.uri())).await;
+
+        assert!(matches!(result, Err(PolarError::Request(_))));
+    }
+
+    #[tokio::test]
+    async fn should_not_follow_redirect_with_none_policy() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/customers/me"))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", "/other"))
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/other"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(0)
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri())
+            .with_redirect_policy(reqwest::redirect::Policy::none())
+            .unwrap();
+
+        let result = polar.get::<Value>("customers/me").await;
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_get_polar_when_arguments_are_valid() {
+        let result = Polar::new("https://sandbox-api.polar.sh/v1/", "123");
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn should_not_get_polar_when_base_url_is_invalid() {
+        let result = Polar::new("/v1/", "123");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_not_get_polar_when_access_token_is_empty() {
+        let result = Polar::new("https://sandbox-api.polar.sh/v1/", "");
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_get_customer_portal_when_token_is_valid() {
+        let polar = get_test_polar("https://sandbox-api.polar.sh/v1/".to_owned());
+
+        let result = polar.customer_portal_from_session_token("polar_cst_123");
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn should_not_get_customer_portal_when_token_is_empty() {
+        let polar = get_test_polar("https://sandbox-api.polar.sh/v1/".to_owned());
+
+        let result = polar.customer_portal_from_session_token("");
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_get_customer_portal_customer_with_customer_token() {
+        let mock = get_mock(
+            "GET",
+            "/customer-portal/customers/me",
+            200,
+            get_fixture::<Value>("customer_portal_customer"),
+        )
+        .await;
+
+        let customer_portal = Polar::with_customer_token(mock.uri(), "polar_cst_123").unwrap();
+
+        let result = customer_portal.get_customer().await;
+
+        assert!(result.is_ok());
+        assert_eq!(
+            mock.received_requests().await.unwrap()[0].headers["authorization"],
+            "Bearer polar_cst_123"
+        );
+    }
+
+    #[test]
+    fn should_not_get_customer_portal_when_customer_token_is_empty() {
+        let result = Polar::with_customer_token("https://sandbox-api.polar.sh/v1/", "");
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_get_customer_portal_customer() {
+        let mock = get_mock(
+            "GET",
+            "/customer-portal/customers/me",
+            200,
+            get_fixture::<Value>("customer_portal_customer"),
+        )
+        .await;
+
+        let customer_portal = get_test_polar(mock.uri())
+            .customer_portal_from_session_token("polar_cst_123")
+            .unwrap();
+
+        let result = customer_portal.get_customer().await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_get_customer_portal_customer() {
+        let mock = get_mock(
+            "GET",
+            "/customer-portal/customers/me",
+            401,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let customer_portal = get_test_polar(mock.uri())
+            .customer_portal_from_session_token("polar_cst_123")
+            .unwrap();
+
+        let result = customer_portal.get_customer().await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_list_customer_portal_downloadables() {
+        let mock = get_mock(
+            "GET",
+            "/customer-portal/downloadables",
+            200,
+            get_fixture::<Value>("downloadables_list"),
+        )
+        .await;
+
+        let customer_portal = get_test_polar(mock.uri())
+            .customer_portal_from_session_token("polar_cst_123")
+            .unwrap();
+
+        let result = customer_portal
+            .list_downloadables(&ListDownloadablesParams::default())
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_fetch_next_page_of_customer_portal_downloadables() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/customer-portal/downloadables"))
+            .and(matchers::query_param("page", "2"))
+            .and(matchers::header("authorization", "Bearer polar_cst_123"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("downloadables_list")))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/customer-portal/downloadables"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("downloadables_list")))
+            .mount(&mock)
+            .await;
+
+        let customer_portal = Polar::with_customer_token(mock.uri(), "polar_cst_123").unwrap();
+        let first = customer_portal
+            .list_downloadables(&ListDownloadablesParams::default())
+            .await
+            .unwrap();
+
+        let result = customer_portal.next_page(&first).await;
+
+        assert!(result.is_ok_and(|second| second.is_some_and(|second| second.page_number() == 2)));
+    }
+
+    fn get_downloadable(download_url: String, expires_at: &str) -> Value {
+        let mut downloadable = get_fixture::<Value>("downloadable");
+
+        downloadable["file"]["download"]["url"] = json!(download_url);
+        downloadable["file"]["download"]["expires_at"] = json!(expires_at);
+
+        downloadable
+    }
+
+    #[tokio::test]
+    async fn should_download_without_refresh() {
+        let mock = get_mock("GET", "/files/current", 200, "<content>").await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/customer-portal/downloadables"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&mock)
+            .await;
+
+        let customer_portal = get_test_polar(mock.uri())
+            .customer_portal_from_session_token("polar_cst_123")
+            .unwrap();
+        let downloadable = from_value(get_downloadable(
+            format!("{}/files/current", mock.uri()),
+            "2099-01-01T00:00:00Z",
+        ))
+        .unwrap();
+
+        let result = customer_portal.download(&downloadable).await;
+
+        assert!(result.is_ok());
+        assert!(
+            mock.received_requests().await.unwrap()[0]
+                .headers
+                .get("authorization")
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_download_with_refresh_when_forbidden() {
+        let mock = MockServer::start().await;
+        let refreshed = get_downloadable(format!("{}/files/new", mock.uri()), "2099-01-01T00:00:00Z");
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/files/old"))
+            .respond_with(ResponseTemplate::new(403))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/customer-portal/downloadables"))
+            .and(matchers::query_param(
+                "benefit_id",
+                refreshed["benefit_id"].as_str().unwrap(),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": [refreshed],
+                "pagination": { "total_count": 1, "max_page": 1 },
+            })))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/files/new"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<content>"))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let customer_portal = get_test_polar(mock.uri())
+            .customer_portal_from_session_token("polar_cst_123")
+            .unwrap();
+        let downloadable = from_value(get_downloadable(
+            format!("{}/files/old", mock.uri()),
+            "2099-01-01T00:00:00Z",
+        ))
+        .unwrap();
+
+        let result = customer_portal.download(&downloadable).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_download_with_refresh_when_expired() {
+        let mock = MockServer::start().await;
+        let refreshed = get_downloadable(format!("{}/files/new", mock.uri()), "2099-01-01T00:00:00Z");
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/customer-portal/downloadables"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": [refreshed],
+                "pagination": { "total_count": 1, "max_page": 1 },
+            })))
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/files/new"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("<content>"))
+            .mount(&mock)
+            .await;
+
+        let customer_portal = get_test_polar(mock.uri())
+            .customer_portal_from_session_token("polar_cst_123")
+            .unwrap();
+        let downloadable = from_value(get_downloadable(
+            format!("{}/files/old", mock.uri()),
+            "2000-01-01T00:00:00Z",
+        ))
+        .unwrap();
+
+        let result = customer_portal.download(&downloadable).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_resume_interrupted_download() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+
+        // The first response is cut after 4 of its 10 bytes, the second one sends the rest.
+        let server = std::thread::spawn(move || {
+            let responses: [&[u8]; 2] = [
+                b"HTTP/1.1 200 OK\r\ncontent-length: 10\r\n\r\n0123",
+                b"HTTP/1.1 206 Partial Content\r\ncontent-length: 6\r\n\r\n456789",
+            ];
+
+            responses
+                .into_iter()
+                .zip(listener.incoming())
+                .map(|(response, stream)| {
+                    let mut stream = stream.unwrap();
+                    let mut buffer = [0; 4096];
+                    let read = stream.read(&mut buffer).unwrap();
+
+                    stream.write_all(response).unwrap();
+
+                    String::from_utf8_lossy(&buffer[..read]).to_lowercase()
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let customer_portal = get_test_polar(format!("http://{address}"))
+            .customer_portal_from_session_token("polar_cst_123")
+            .unwrap();
+        let downloadable = from_value(get_downloadable(
+            format!("http://{address}/files/current"),
+            "2099-01-01T00:00:00Z",
+        ))
+        .unwrap();
+        let mut content = Vec::new();
+
+        let result = customer_portal.download_into(&downloadable, &mut content).await;
+
+        assert!(result.is_ok_and(|written| written == 10));
+        assert_eq!(content, b"0123456789");
+        assert!(server.join().unwrap()[1].contains("range: bytes=4-"));
+    }
+
+    #[tokio::test]
+    async fn should_not_download_when_refresh_fails() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/files/old"))
+            .respond_with(ResponseTemplate::new(410))
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/customer-portal/downloadables"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": [],
+                "pagination": { "total_count": 0, "max_page": 1 },
+            })))
+            .mount(&mock)
+            .await;
+
+        let customer_portal = get_test_polar(mock.uri())
+            .customer_portal_from_session_token("polar_cst_123")
+            .unwrap();
+        let downloadable = from_value(get_downloadable(
+            format!("{}/files/old", mock.uri()),
+            "2099-01-01T00:00:00Z",
+        ))
+        .unwrap();
+
+        let result = customer_portal.download(&downloadable).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_report_unknown_fields() {
+        let customer_id = Uuid::new_v4();
+        let mut customer = get_fixture::<Value>("customer");
+
+        customer["new_field"] = json!({ "name": "<string>" });
+
+        let mock = get_mock("GET", &format!("/customers/{}", customer_id), 200, customer).await;
+        let unknown_fields = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let polar = get_test_polar(mock.uri()).with_unknown_field_hook({
+            let unknown_fields = unknown_fields.clone();
+
+            move |unknown: &UnknownField| unknown_fields.lock().unwrap().push(unknown.clone())
+        });
+
+        let result = polar.get_customer(customer_id).await;
+
+        assert!(result.is_ok());
+        assert_eq!(
+            *unknown_fields.lock().unwrap(),
+            vec![UnknownField {
+                path: "new_field".to_owned(),
+                kind: UnknownFieldKind::Field,
+                value: json!({ "name": "<string>" }),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_report_unknown_enum_values() {
+        let payment_id = Uuid::new_v4();
+        let mut payment = get_fixture::<Value>("payment");
+
+        payment["decline_reason"] = json!("new_reason");
+
+        let mock = get_mock("GET", &format!("/payments/{}", payment_id), 200, payment).await;
+        let unknown_fields = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let polar = get_test_polar(mock.uri()).with_unknown_field_hook({
+            let unknown_fields = unknown_fields.clone();
+
+            move |unknown: &UnknownField| unknown_fields.lock().unwrap().push(unknown.clone())
+        });
+
+        let result = polar.get_payment(payment_id).await;
+
+        assert!(result.is_ok_and(|payment| payment.decline_reason == Some(PaymentDeclineReason::Unknown)));
+        assert_eq!(
+            unknown_fields
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|unknown| unknown.kind == UnknownFieldKind::Variant)
+                .collect::<Vec<_>>(),
+            vec![&UnknownField {
+                path: "decline_reason".to_owned(),
+                kind: UnknownFieldKind::Variant,
+                value: json!("new_reason"),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_get_as_partial_model() {
+        #[derive(Deserialize)]
+        struct ProductName {
+            name: String,
+        }
+
+        let product_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/products/{}", product_id),
+            200,
+            get_fixture::<Value>("product"),
+        )
+        .await;
+        let polar = get_test_polar(mock.uri());
+
+        let result: PolarResult<ProductName> = polar.get_as(&format!("products/{product_id}")).await;
+
+        assert_eq!(result.unwrap().name, get_fixture::<Value>("product")["name"]);
+    }
+
+    #[tokio::test]
+    async fn should_get_list_as_partial_model() {
+        #[derive(Deserialize)]
+        struct ProductId {
+            id: Uuid,
+        }
+
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/products"))
+            .and(matchers::query_param("is_archived", "false"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("products_list")))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result: PolarResult<ListResponse<ProductId>> = polar
+            .get_list_as(
+                "products",
+                &ListProductsParams {
+                    is_archived: Some(false),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        assert!(result.is_ok_and(|products| products.items.iter().all(|product| !product.id.is_nil())));
+    }
+
+    #[tokio::test]
+    async fn should_call_request_hook() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/customers"))
+            .and(matchers::header("x-body-length", "2"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(get_fixture::<Value>("customer")))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri()).with_request_hook(|request| {
+            let length = request.body().and_then(|body| body.as_bytes()).map_or(0, <[u8]>::len);
+
+            request.headers_mut().insert("x-body-length", length.into());
+        });
+
+        let result: PolarResult<Customer> = polar.post("customers", &json!({})).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_report_deprecation() {
+        let customer_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path(format!("/customers/{}", customer_id)))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("deprecation", "@1735689600")
+                    .insert_header("sunset", "Wed, 31 Dec 2025 23:59:59 GMT")
+                    .set_body_json(get_fixture::<Value>("customer")),
+            )
+            .mount(&mock)
+            .await;
+
+        let deprecations = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let polar = get_test_polar(mock.uri()).with_deprecation_hook({
+            let deprecations = deprecations.clone();
+
+            move |deprecation| deprecations.lock().unwrap().push(deprecation.clone())
+        });
+
+        let result = polar.get_customer(customer_id).await;
+
+        assert!(result.is_ok());
+
+        let deprecations = deprecations.lock().unwrap();
+
+        assert_eq!(deprecations.len(), 1);
+        assert_eq!(deprecations[0].method, reqwest::Method::GET);
+        assert_eq!(deprecations[0].url.path(), format!("/customers/{}", customer_id));
+        assert_eq!(deprecations[0].deprecation.as_deref(), Some("@1735689600"));
+        assert_eq!(deprecations[0].sunset, DateTime::from_timestamp(1_767_225_599, 0));
+    }
+
+    #[tokio::test]
+    async fn should_get_empty_body() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/empty"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let unit_result: PolarResult<()> = polar.get("empty").await;
+        let option_result: PolarResult<Option<Customer>> = polar.get("empty").await;
+
+        assert!(unit_result.is_ok());
+        assert!(option_result.is_ok_and(|customer| customer.is_none()));
+    }
+
+    #[tokio::test]
+    async fn should_delete_with_null_body() {
+        let mock = get_mock("DELETE", "/null", 200, Value::Null).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result: PolarResult<Option<Customer>> = polar.delete("null").await;
+
+        assert!(result.is_ok_and(|customer| customer.is_none()));
+    }
+
+    #[tokio::test]
+    async fn should_not_get_empty_body_as_model() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/empty"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result: PolarResult<Customer> = polar.get("empty").await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_create_checkout_session() {
+        let mock = get_mock("POST", "/checkouts", 201, get_fixture::<Value>("checkout_session")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("checkout_session_params");
+
+        let result = polar.create_checkout_session(&params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_create_checkout_session() {
+        let mock = get_mock("POST", "/checkouts", 422, get_fixture::<Value>("unprocessable_entity")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("checkout_session_params");
+
+        let result = polar.create_checkout_session(&params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_get_checkout_session() {
+        let checkout_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/checkouts/{}", checkout_id),
+            200,
+            get_fixture::<Value>("checkout_session"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_checkout_session(checkout_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_get_checkout_session() {
+        let checkout_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/checkouts/{}", checkout_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_checkout_session(checkout_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_list_checkout_sessions() {
+        let mock = get_mock("GET", "/checkouts", 200, get_fixture::<Value>("checkout_sessions_list")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .list_checkout_sessions(&ListCheckoutSessionsParams::default())
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_get_checkout_link() {
+        let checkout_link_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/checkout-links/{}", checkout_link_id),
+            200,
+            get_fixture::<Value>("checkout_link"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_checkout_link(checkout_link_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_get_checkout_link() {
+        let checkout_link_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/checkout-links/{}", checkout_link_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_checkout_link(checkout_link_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_update_checkout_link() {
+        let checkout_link_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/checkout-links/{}", checkout_link_id),
+            200,
+            get_fixture::<Value>("checkout_link"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_checkout_link_params");
+
+        let result = polar.update_checkout_link(checkout_link_id, &params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_update_checkout_link() {
+        let checkout_link_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/checkout-links/{}", checkout_link_id),
+            422,
+            get_fixture::<Value>("unprocessable_entity"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_checkout_link_params");
+
+        let result = polar.update_checkout_link(checkout_link_id, &params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_delete_checkout_link() {
+        let checkout_link_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("DELETE"))
+            .and(matchers::path(format!("/checkout-links/{}", checkout_link_id)))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.delete_checkout_link(checkout_link_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_delete_checkout_link() {
+        let checkout_link_id = Uuid::new_v4();
+        let mock = get_mock(
+            "DELETE",
+            &format!("/checkout-links/{}", checkout_link_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.delete_checkout_link(checkout_link_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_create_customer() {
+        let mock = get_mock("POST", "/customers", 201, get_fixture::<Value>("customer")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("customer_params");
+
+        let result = polar.create_customer(&params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_create_customer() {
+        let mock = get_mock("POST", "/customers", 422, get_fixture::<Value>("unprocessable_entity")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("customer_params");
+
+        let result = polar.create_customer(&params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_list_customers() {
+        let mock = get_mock("GET", "/customers", 200, get_fixture::<Value>("customers_list")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.list_customers(&ListCustomersParams::default()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_get_customer() {
+        let customer_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/customers/{}", customer_id),
+            200,
+            get_fixture::<Value>("customer"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_customer(customer_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_get_customer() {
+        let customer_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/customers/{}", customer_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_customer(customer_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_update_customer() {
+        let customer_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/customers/{}", customer_id),
+            200,
+            get_fixture::<Value>("customer"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_customer_params");
+
+        let result = polar.update_customer(customer_id, &params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_update_customer() {
+        let customer_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/customers/{}", customer_id),
+            422,
+            get_fixture::<Value>("unprocessable_entity"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_customer_params");
+
+        let result = polar.update_customer(customer_id, &params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_delete_customer() {
+        let customer_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("DELETE"))
+            .and(matchers::path(format!("/customers/{}", customer_id)))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.delete_customer(customer_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_delete_customer() {
+        let customer_id = Uuid::new_v4();
+        let mock = get_mock(
+            "DELETE",
+            &format!("/customers/{}", customer_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.delete_customer(customer_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_get_customer_by_external_id() {
+        let mock = get_mock(
+            "GET",
+            "/customers/external/usr_1337",
+            200,
+            get_fixture::<Value>("customer"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_customer_by_external_id("usr_1337").await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_get_customer_by_external_id() {
+        let mock = get_mock(
+            "GET",
+            "/customers/external/usr_1337",
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_customer_by_external_id("usr_1337").await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_update_customer_by_external_id() {
+        let mock = get_mock(
+            "PATCH",
+            "/customers/external/usr_1337",
+            200,
+            get_fixture::<Value>("customer"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_customer_params");
+
+        let result = polar.update_customer_by_external_id("usr_1337", &params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_update_customer_by_external_id() {
+        let mock = get_mock(
+            "PATCH",
+            "/customers/external/usr_1337",
+            422,
+            get_fixture::<Value>("unprocessable_entity"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_customer_params");
+
+        let result = polar.update_customer_by_external_id("usr_1337", &params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_sync_customer_identity() {
+        let mock = get_mock(
+            "GET",
+            "/customers/external/usr_1337",
+            200,
+            get_fixture::<Value>("customer"),
+        )
+        .await;
+
+        Mock::given(matchers::method("PATCH"))
+            .and(matchers::path("/customers/external/usr_1337"))
+            .and(matchers::body_json(json!({ "name": "Jane Doe" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("customer")))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .sync_customer_identity("usr_1337", Some("Jane Doe"), "Customer@example.com")
+            .await;
+
+        assert!(result.is_ok_and(|updated| updated));
+    }
+
+    #[tokio::test]
+    async fn should_not_sync_unchanged_customer_identity() {
+        let mock = get_mock(
+            "GET",
+            "/customers/external/usr_1337",
+            200,
+            get_fixture::<Value>("customer"),
+        )
+        .await;
+
+        Mock::given(matchers::method("PATCH"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("customer")))
+            .expect(0)
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .sync_customer_identity("usr_1337", Some("John Doe"), "customer@example.com")
+            .await;
+
+        assert!(result.is_ok_and(|updated| !updated));
+    }
+
+    #[tokio::test]
+    async fn should_get_customer_state_by_external_id() {
+        let mock = get_mock(
+            "GET",
+            "/customers/external/usr_1337/state",
+            200,
+            get_fixture::<Value>("customer_state"),
+        )
+        .await;
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_customer_state_by_external_id("usr_1337").await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_check_customer_benefits_and_products() {
+        let customer_state = get_fixture::<Value>("customer_state");
+        let benefit_id = customer_state["granted_benefits"][0]["benefit_id"].as_str().unwrap();
+        let product_id = customer_state["active_subscriptions"][0]["product_id"]
+            .as_str()
+            .unwrap();
+        let mock = get_mock("GET", "/customers/external/usr_1337/state", 200, &customer_state).await;
+        let polar = get_test_polar(mock.uri());
+
+        assert!(
+            polar
+                .customer_has_benefit("usr_1337", benefit_id.parse().unwrap())
+                .await
+                .unwrap()
+        );
+        assert!(!polar.customer_has_benefit("usr_1337", Uuid::new_v4()).await.unwrap());
+        assert!(
+            polar
+                .customer_has_product("usr_1337", product_id.parse().unwrap())
+                .await
+                .unwrap()
+        );
+        assert!(!polar.customer_has_product("usr_1337", Uuid::new_v4()).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn should_not_grant_benefits_to_unknown_customer() {
+        let mock = get_mock(
+            "GET",
+            "/customers/external/usr_1337/state",
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.customer_has_benefit("usr_1337", Uuid::new_v4()).await;
+
+        assert!(result.is_ok_and(|has_benefit| !has_benefit));
+    }
+
+    #[tokio::test]
+    async fn should_cache_customer_state() {
+        #[derive(Default)]
+        struct MemoryCache(std::sync::Mutex<HashMap<String, Arc<CustomerState>>>);
+
+        impl CustomerStateCache for MemoryCache {
+            fn get(&self, external_id: &str) -> Option<Arc<CustomerState>> {
+                self.0.lock().unwrap().get(external_id).cloned()
+            }
+
+            fn insert(&self, external_id: &str, state: Arc<CustomerState>) {
+                self.0.lock().unwrap().insert(external_id.to_owned(), state);
+            }
+        }
+
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/customers/external/usr_1337/state"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("customer_state")))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri()).with_customer_state_cache(MemoryCache::default());
+
+        for _ in 0..3 {
+            assert!(polar.customer_has_product("usr_1337", Uuid::new_v4()).await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn should_delete_customer_by_external_id() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("DELETE"))
+            .and(matchers::path("/customers/external/usr_1337"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.delete_customer_by_external_id("usr_1337").await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_delete_customer_by_external_id() {
+        let mock = get_mock(
+            "DELETE",
+            "/customers/external/usr_1337",
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.delete_customer_by_external_id("usr_1337").await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_encode_external_id_with_slashes_and_spaces() {
+        let mock = MockServer::start().await;
+
+        for method in ["GET", "PATCH"] {
+            Mock::given(matchers::method(method))
+                .and(matchers::path("/customers/external/org%2Fusr%201337"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("customer")))
+                .expect(1)
+                .mount(&mock)
+                .await;
+        }
+
+        Mock::given(matchers::method("DELETE"))
+            .and(matchers::path("/customers/external/org%2Fusr%201337"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+        let external_id = "org/usr 1337";
+
+        assert!(polar.get_customer_by_external_id(external_id).await.is_ok());
+        assert!(
+            polar
+                .update_customer_by_external_id(external_id, &UpdateCustomerParams::default())
+                .await
+                .is_ok()
+        );
+        assert!(polar.delete_customer_by_external_id(external_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_address_customer_with_dot_segment_external_id() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("customer")))
+            .expect(0)
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        for external_id in ["", ".", ".."] {
+            let result = polar.get_customer_by_external_id(external_id).await;
+
+            assert!(matches!(result, Err(PolarError::Request(_))));
+        }
+
+        assert!(polar.delete_customer_by_external_id("..").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_list_orders() {
+        let mock = get_mock("GET", "/orders", 200, get_fixture::<Value>("orders_list")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.list_orders(&ListOrdersParams::default()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_get_order() {
+        let order_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/orders/{}", order_id),
+            200,
+            get_fixture::<Value>("order"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_order(order_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_get_order() {
+        let order_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/orders/{}", order_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_order(order_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_update_order() {
+        let order_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/orders/{}", order_id),
+            200,
+            get_fixture::<Value>("order"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_order_params");
+
+        let result = polar.update_order(order_id, &params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_update_order() {
+        let order_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/orders/{}", order_id),
+            422,
+            get_fixture::<Value>("unprocessable_entity"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_order_params");
+
+        let result = polar.update_order(order_id, &params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_generate_order_invoice() {
+        let order_id = Uuid::new_v4();
+        let mock = get_mock("POST", &format!("/orders/{}/invoice", order_id), 202, json!({})).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.generate_order_invoice(order_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_generate_order_invoice() {
+        let order_id = Uuid::new_v4();
+        let mock = get_mock(
+            "POST",
+            &format!("/orders/{}/invoice", order_id),
+            422,
+            get_fixture::<Value>("unprocessable_entity"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.generate_order_invoice(order_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_retry_order_payment() {
+        let order_id = Uuid::new_v4();
+        let mock = get_mock("POST", &format!("/orders/{}/retry-payment", order_id), 202, json!({})).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.retry_order_payment(order_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_order_payment() {
+        let order_id = Uuid::new_v4();
+        let mock = get_mock(
+            "POST",
+            &format!("/orders/{}/retry-payment", order_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.retry_order_payment(order_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_get_order_invoice() {
+        let order_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/orders/{}/invoice", order_id),
+            200,
+            get_fixture::<Value>("order_invoice"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_order_invoice(order_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_get_order_invoice() {
+        let order_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/orders/{}/invoice", order_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_order_invoice(order_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_get_subscription() {
+        let subscription_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/subscriptions/{}", subscription_id),
+            200,
+            get_fixture::<Value>("subscription"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_subscription(subscription_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_get_subscription() {
+        let subscription_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/subscriptions/{}", subscription_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_subscription(subscription_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_list_subscriptions() {
+        let mock = get_mock("GET", "/subscriptions", 200, get_fixture::<Value>("subscriptions_list")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.list_subscriptions(&ListSubscriptionsParams::default()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_list_subscriptions_with_repeated_filters() {
+        let organization_ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let customer_ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/subscriptions"))
+            .and(matchers::query_param(
+                "organization_id",
+                organization_ids[0].to_string(),
+            ))
+            .and(matchers::query_param(
+                "organization_id",
+                organization_ids[1].to_string(),
+            ))
+            .and(matchers::query_param("customer_id", customer_ids[0].to_string()))
+            .and(matchers::query_param("customer_id", customer_ids[1].to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("subscriptions_list")))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .list_subscriptions(&ListSubscriptionsParams {
+                organization_id: Some(organization_ids.to_vec()),
+                customer_id: Some(customer_ids.to_vec()),
+                ..Default::default()
+            })
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_update_subscription() {
+        let subscription_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/subscriptions/{}", subscription_id),
+            200,
+            get_fixture::<Value>("subscription"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("subscription_params");
+
+        let result = polar.update_subscription(subscription_id, &params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_update_subscription() {
+        let subscription_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/subscriptions/{}", subscription_id),
+            422,
+            get_fixture::<Value>("unprocessable_entity"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("subscription_params");
+
+        let result = polar.update_subscription(subscription_id, &params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_revoke_subscription() {
+        let subscription_id = Uuid::new_v4();
+        let mock = get_mock(
+            "DELETE",
+            &format!("/subscriptions/{}", subscription_id),
+            200,
+            get_fixture::<Value>("subscription"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.revoke_subscription(subscription_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_revoke_subscription() {
+        let subscription_id = Uuid::new_v4();
+        let mock = get_mock(
+            "DELETE",
+            &format!("/subscriptions/{}", subscription_id),
+            422,
+            get_fixture::<Value>("unprocessable_entity"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.revoke_subscription(subscription_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_get_product() {
+        let product_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/products/{}", product_id),
+            200,
+            get_fixture::<Value>("product"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_product(product_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_get_product() {
+        let product_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/products/{}", product_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_product(product_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_list_products() {
+        let mock = get_mock("GET", "/products", 200, get_fixture::<Value>("products_list")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.list_products(&ListProductsParams::default()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_list_products_with_filters() {
+        let benefit_ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/products"))
+            .and(matchers::query_param("is_archived", "false"))
+            .and(matchers::query_param("is_recurring", "true"))
+            .and(matchers::query_param("benefit_id", benefit_ids[0].to_string()))
+            .and(matchers::query_param("benefit_id", benefit_ids[1].to_string()))
+            .and(matchers::query_param("metadata[plan]", "pro"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("products_list")))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .list_products(&ListProductsParams {
+                is_archived: Some(false),
+                is_recurring: Some(true),
+                benefit_id: Some(benefit_ids.to_vec()),
+                metadata: Some(HashMap::from([("plan".to_owned(), "pro".to_owned())])),
+                ..Default::default()
+            })
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_list_products_if_modified_since() {
+        let mock = get_mock("GET", "/products", 200, get_fixture::<Value>("products_list")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let since = "2023-11-01T00:00:00Z".parse().unwrap();
+
+        let result = polar
+            .list_products_if_modified_since(&ListProductsParams::default(), since)
+            .await;
+
+        assert!(result.is_ok_and(|products| products.is_some()));
+    }
+
+    #[tokio::test]
+    async fn should_not_list_products_if_not_modified_since() {
+        let mock = get_mock("GET", "/products", 200, get_fixture::<Value>("products_list")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .list_products_if_modified_since(&ListProductsParams::default(), Utc::now())
+            .await;
+
+        assert!(result.is_ok_and(|products| products.is_none()));
+    }
+
+    #[tokio::test]
+    async fn should_create_product() {
+        let mock = get_mock("POST", "/products", 201, get_fixture::<Value>("product")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("product_params");
+
+        let result = polar.create_product(&params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_create_product() {
+        let mock = get_mock("POST", "/products", 422, get_fixture::<Value>("unprocessable_entity")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("product_params");
+
+        let result = polar.create_product(&params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_update_product() {
+        let product_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/products/{}", product_id),
+            200,
+            get_fixture::<Value>("product"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_product_params");
+
+        let result = polar.update_product(product_id, &params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_update_product() {
+        let product_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/products/{}", product_id),
+            422,
+            get_fixture::<Value>("unprocessable_entity"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_product_params");
+
+        let result = polar.update_product(product_id, &params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_update_product_benefits() {
+        let product_id = Uuid::new_v4();
+        let mock = get_mock(
+            "POST",
+            &format!("/products/{}/benefits", product_id),
+            200,
+            get_fixture::<Value>("product"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_product_benefits_params");
+
+        let result = polar.update_product_benefits(product_id, &params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_update_product_benefits() {
+        let product_id = Uuid::new_v4();
+        let mock = get_mock(
+            "POST",
+            &format!("/products/{}/benefits", product_id),
+            422,
+            get_fixture::<Value>("unprocessable_entity"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_product_benefits_params");
+
+        let result = polar.update_product_benefits(product_id, &params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_create_benefit() {
+        let mock = get_mock("POST", "/benefits", 201, get_fixture::<Value>("benefit")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("benefit_create_params");
+
+        let result = polar.create_benefit(&params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_create_benefit() {
+        let mock = get_mock("POST", "/benefits", 422, get_fixture::<Value>("unprocessable_entity")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("benefit_create_params");
+
+        let result = polar.create_benefit(&params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_list_benefits() {
+        let mock = get_mock("GET", "/benefits", 200, get_fixture::<Value>("benefits_list")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.list_benefits(&ListBenefitsParams::default()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_list_benefits_with_filters() {
+        let organization_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/benefits"))
+            .and(matchers::query_param("organization_id", organization_id.to_string()))
+            .and(matchers::query_param("type_filter", "license_keys"))
+            .and(matchers::query_param("type_filter", "downloadables"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("benefits_list")))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .list_benefits(&ListBenefitsParams {
+                organization_id: Some(vec![organization_id]),
+                type_filter: Some(vec![BenefitType::LicenseKeys, BenefitType::Downloadables]),
+                ..Default::default()
+            })
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_list_benefits_if_modified_since() {
+        let mock = get_mock("GET", "/benefits", 200, get_fixture::<Value>("benefits_list")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let since = "2023-11-01T00:00:00Z".parse().unwrap();
+
+        let result = polar
+            .list_benefits_if_modified_since(&ListBenefitsParams::default(), since)
+            .await;
+
+        assert!(result.is_ok_and(|benefits| benefits.is_some()));
+    }
+
+    #[tokio::test]
+    async fn should_not_list_benefits_if_not_modified_since() {
+        let mock = get_mock("GET", "/benefits", 200, get_fixture::<Value>("benefits_list")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .list_benefits_if_modified_since(&ListBenefitsParams::default(), Utc::now())
+            .await;
+
+        assert!(result.is_ok_and(|benefits| benefits.is_none()));
+    }
+
+    #[tokio::test]
+    async fn should_get_benefit() {
+        let benefit_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/benefits/{}", benefit_id),
+            200,
+            get_fixture::<Value>("benefit"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_benefit(benefit_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_get_benefit() {
+        let benefit_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/benefits/{}", benefit_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_benefit(benefit_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_update_benefit() {
+        let benefit_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/benefits/{}", benefit_id),
+            200,
+            get_fixture::<Value>("benefit"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_benefit_params");
+
+        let result = polar.update_benefit(benefit_id, &params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_update_benefit() {
+        let benefit_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/benefits/{}", benefit_id),
+            422,
+            get_fixture::<Value>("unprocessable_entity"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_benefit_params");
+
+        let result = polar.update_benefit(benefit_id, &params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_delete_benefit() {
+        let benefit_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("DELETE"))
+            .and(matchers::path(format!("/benefits/{}", benefit_id)))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.delete_benefit(benefit_id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_delete_benefit() {
+        let benefit_id = Uuid::new_v4();
+        let mock = get_mock(
+            "DELETE",
+            &format!("/benefits/{}", benefit_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.delete_benefit(benefit_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_create_discount() {
+        let mock = get_mock("POST", "/discounts", 201, get_fixture::<Value>("discount")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("discount_params");
+
+        let result = polar.create_discount(&params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_create_discount() {
+        let mock = get_mock("POST", "/discounts", 422, get_fixture::<Value>("unprocessable_entity")).await;
 
-    use serde_json::from_reader;
-    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+        let polar = get_test_polar(mock.uri());
 
-    use super::*;
+        let params = get_fixture("discount_params");
 
-    fn get_fixture<T: DeserializeOwned>(name: &str) -> T {
-        let file = File::open(format!("fixtures/{name}.json")).unwrap();
-        let reader = BufReader::new(file);
+        let result = polar.create_discount(&params).await;
 
-        from_reader(reader).unwrap()
+        assert!(result.is_err());
     }
 
-    async fn get_mock<B: Serialize>(method: &str, path: &str, status_code: u16, body: B) -> MockServer {
-        let mock_server = MockServer::start().await;
+    #[tokio::test]
+    async fn should_list_discounts() {
+        let mock = get_mock("GET", "/discounts", 200, get_fixture::<Value>("discounts_list")).await;
 
-        Mock::given(matchers::method(method))
-            .and(matchers::path(path))
-            .respond_with(ResponseTemplate::new(status_code).set_body_json(body))
-            .mount(&mock_server)
-            .await;
+        let polar = get_test_polar(mock.uri());
 
-        mock_server
-    }
+        let result = polar.list_discounts(&ListDiscountsParams::default()).await;
 
-    fn get_test_polar(base_url: String) -> Polar {
-        Polar::new(base_url, "123").ok().unwrap()
+        assert!(result.is_ok());
     }
 
-    #[test]
-    fn should_get_polar_when_arguments_are_valid() {
-        let result = Polar::new("https://sandbox-api.polar.sh/v1/", "123");
+    #[tokio::test]
+    async fn should_list_discounts_with_filters() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/discounts"))
+            .and(matchers::query_param("query", "SUMMER"))
+            .and(matchers::query_param("sorting", "-redemptions_count"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("discounts_list")))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .list_discounts(&ListDiscountsParams {
+                query: Some("SUMMER".to_owned()),
+                sorting: Some(vec![DiscountsSorting::RedemptionsCountDesc]),
+                ..Default::default()
+            })
+            .await;
 
         assert!(result.is_ok());
     }
 
-    #[test]
-    fn should_not_get_polar_when_base_url_is_invalid() {
-        let result = Polar::new("/v1/", "123");
+    #[tokio::test]
+    async fn should_get_discount() {
+        let discount_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/discounts/{}", discount_id),
+            200,
+            get_fixture::<Value>("discount"),
+        )
+        .await;
 
-        assert!(result.is_err());
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_discount(discount_id).await;
+
+        assert!(result.is_ok());
     }
 
-    #[test]
-    fn should_not_get_polar_when_access_token_is_empty() {
-        let result = Polar::new("https://sandbox-api.polar.sh/v1/", "");
+    #[tokio::test]
+    async fn should_not_get_discount() {
+        let discount_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/discounts/{}", discount_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_discount(discount_id).await;
 
         assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn should_create_checkout_session() {
-        let mock = get_mock("POST", "/checkouts", 201, get_fixture::<Value>("checkout_session")).await;
+    async fn should_update_discount() {
+        let discount_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/discounts/{}", discount_id),
+            200,
+            get_fixture::<Value>("discount"),
+        )
+        .await;
 
         let polar = get_test_polar(mock.uri());
 
-        let params = get_fixture("checkout_session_params");
+        let params = get_fixture("update_discount_params");
 
-        let result = polar.create_checkout_session(&params).await;
+        let result = polar.update_discount(discount_id, &params).await;
 
         assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn should_not_create_checkout_session() {
-        let mock = get_mock("POST", "/checkouts", 422, get_fixture::<Value>("unprocessable_entity")).await;
+    async fn should_not_update_discount() {
+        let discount_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/discounts/{}", discount_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
 
         let polar = get_test_polar(mock.uri());
 
-        let params = get_fixture("checkout_session_params");
+        let params = get_fixture("update_discount_params");
 
-        let result = polar.create_checkout_session(&params).await;
+        let result = polar.update_discount(discount_id, &params).await;
 
         assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn should_get_checkout_session() {
-        let checkout_id = Uuid::new_v4();
-        let mock = get_mock(
-            "GET",
-            &format!("/checkouts/{}", checkout_id),
-            200,
-            get_fixture::<Value>("checkout_session"),
-        )
-        .await;
+    async fn should_delete_discount() {
+        let discount_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("DELETE"))
+            .and(matchers::path(format!("/discounts/{}", discount_id)))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock)
+            .await;
 
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.get_checkout_session(checkout_id).await;
+        let result = polar.delete_discount(discount_id).await;
 
         assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn should_not_get_checkout_session() {
-        let checkout_id = Uuid::new_v4();
+    async fn should_not_delete_discount() {
+        let discount_id = Uuid::new_v4();
         let mock = get_mock(
-            "GET",
-            &format!("/checkouts/{}", checkout_id),
+            "DELETE",
+            &format!("/discounts/{}", discount_id),
             404,
             get_fixture::<Value>("not_found"),
         )
@@ -472,48 +3709,74 @@ mod tests {
 
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.get_checkout_session(checkout_id).await;
+        let result = polar.delete_discount(discount_id).await;
 
         assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn should_list_checkout_sessions() {
-        let mock = get_mock("GET", "/checkouts", 200, get_fixture::<Value>("checkout_sessions_list")).await;
+    async fn should_list_license_keys() {
+        let mock = get_mock("GET", "/license-keys", 200, get_fixture::<Value>("license_keys_list")).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.list_license_keys(&ListLicenseKeysParams::default()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_list_license_keys_with_filters() {
+        let benefit_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/license-keys"))
+            .and(matchers::query_param("benefit_id", benefit_id.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("license_keys_list")))
+            .mount(&mock)
+            .await;
 
         let polar = get_test_polar(mock.uri());
 
         let result = polar
-            .list_checkout_sessions(&ListCheckoutSessionsParams::default())
+            .list_license_keys(&ListLicenseKeysParams {
+                benefit_id: Some(vec![benefit_id]),
+                ..Default::default()
+            })
             .await;
 
         assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn should_get_subscription() {
-        let subscription_id = Uuid::new_v4();
+    async fn should_get_license_key() {
+        let license_key_id = Uuid::new_v4();
         let mock = get_mock(
             "GET",
-            &format!("/subscriptions/{}", subscription_id),
+            &format!("/license-keys/{}", license_key_id),
             200,
-            get_fixture::<Value>("subscription"),
+            get_fixture::<Value>("license_key_with_activations"),
         )
         .await;
 
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.get_subscription(subscription_id).await;
+        let result = polar.get_license_key(license_key_id).await;
 
-        assert!(result.is_ok());
+        assert!(result.is_ok_and(|license_key| {
+            license_key
+                .activations
+                .is_some_and(|activations| activations.len() == 1)
+        }));
     }
 
     #[tokio::test]
-    async fn should_not_get_subscription() {
-        let subscription_id = Uuid::new_v4();
+    async fn should_not_get_license_key() {
+        let license_key_id = Uuid::new_v4();
         let mock = get_mock(
             "GET",
-            &format!("/subscriptions/{}", subscription_id),
+            &format!("/license-keys/{}", license_key_id),
             404,
             get_fixture::<Value>("not_found"),
         )
@@ -521,243 +3784,456 @@ mod tests {
 
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.get_subscription(subscription_id).await;
+        let result = polar.get_license_key(license_key_id).await;
 
         assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn should_list_subscriptions() {
-        let mock = get_mock("GET", "/subscriptions", 200, get_fixture::<Value>("subscriptions_list")).await;
+    async fn should_update_license_key() {
+        let license_key_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/license-keys/{}", license_key_id),
+            200,
+            get_fixture::<Value>("license_key"),
+        )
+        .await;
 
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.list_subscriptions(&ListSubscriptionsParams::default()).await;
+        let params = get_fixture("update_license_key_params");
+
+        let result = polar.update_license_key(license_key_id, &params).await;
 
         assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn should_update_subscription() {
-        let subscription_id = Uuid::new_v4();
+    async fn should_not_update_license_key() {
+        let license_key_id = Uuid::new_v4();
         let mock = get_mock(
             "PATCH",
-            &format!("/subscriptions/{}", subscription_id),
+            &format!("/license-keys/{}", license_key_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_license_key_params");
+
+        let result = polar.update_license_key(license_key_id, &params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_get_license_key_activation() {
+        let license_key_id = Uuid::new_v4();
+        let activation_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/license-keys/{}/activations/{}", license_key_id, activation_id),
             200,
-            get_fixture::<Value>("subscription"),
+            get_fixture::<Value>("license_key_activation"),
         )
         .await;
 
         let polar = get_test_polar(mock.uri());
 
-        let params = get_fixture("subscription_params");
+        let result = polar.get_license_key_activation(license_key_id, activation_id).await;
 
-        let result = polar.update_subscription(subscription_id, &params).await;
+        assert!(result.is_ok_and(|activation| activation.license_key.is_some()));
+    }
+
+    #[tokio::test]
+    async fn should_not_get_license_key_activation() {
+        let license_key_id = Uuid::new_v4();
+        let activation_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/license-keys/{}/activations/{}", license_key_id, activation_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_license_key_activation(license_key_id, activation_id).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_list_files() {
+        let mock = get_mock("GET", "/files", 200, get_fixture::<Value>("files_list")).await;
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.list_files(&ListFilesParams::default()).await;
 
         assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn should_not_update_subscription() {
-        let subscription_id = Uuid::new_v4();
+    async fn should_list_files_with_filters() {
+        let file_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/files"))
+            .and(matchers::query_param("ids", file_id.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("files_list")))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .list_files(&ListFilesParams {
+                ids: Some(vec![file_id]),
+                ..Default::default()
+            })
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_list_payments() {
+        let mock = get_mock("GET", "/payments", 200, get_fixture::<Value>("payments_list")).await;
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.list_payments(&ListPaymentsParams::default()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_list_payments_with_filters() {
+        let order_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/payments"))
+            .and(matchers::query_param("order_id", order_id.to_string()))
+            .and(matchers::query_param("status", "failed"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("payments_list")))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .list_payments(&ListPaymentsParams {
+                order_id: Some(vec![order_id]),
+                status: Some(vec![PaymentStatus::Failed]),
+                ..Default::default()
+            })
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_get_payment() {
+        let payment_id = Uuid::new_v4();
         let mock = get_mock(
-            "PATCH",
-            &format!("/subscriptions/{}", subscription_id),
-            422,
-            get_fixture::<Value>("unprocessable_entity"),
+            "GET",
+            &format!("/payments/{}", payment_id),
+            200,
+            get_fixture::<Value>("payment"),
         )
         .await;
+        let polar = get_test_polar(mock.uri());
+
+        let payment = polar.get_payment(payment_id).await.unwrap();
+
+        assert_eq!(
+            payment.decline_reason.map(|reason| reason.category()),
+            Some(PaymentDeclineCategory::InsufficientFunds)
+        );
+        assert!(
+            payment
+                .card_metadata()
+                .is_some_and(|card| card.brand == CardBrand::Visa && card.last4 == "4242")
+        );
+    }
 
+    #[tokio::test]
+    async fn should_get_generic_payment() {
+        let payment_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/payments/{}", payment_id),
+            200,
+            get_fixture::<Value>("payment_generic"),
+        )
+        .await;
         let polar = get_test_polar(mock.uri());
 
-        let params = get_fixture("subscription_params");
+        let payment = polar.get_payment(payment_id).await.unwrap();
 
-        let result = polar.update_subscription(subscription_id, &params).await;
+        assert_eq!(payment.method, "ideal");
+        assert!(payment.method_metadata.is_some());
+        assert!(payment.card_metadata().is_none());
+    }
+
+    #[tokio::test]
+    async fn should_not_get_payment() {
+        let payment_id = Uuid::new_v4();
+        let mock = get_mock(
+            "GET",
+            &format!("/payments/{}", payment_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.get_payment(payment_id).await;
 
         assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn should_revoke_subscription() {
-        let subscription_id = Uuid::new_v4();
+    async fn should_create_file() {
+        let mock = get_mock("POST", "/files", 201, get_fixture::<Value>("file_upload")).await;
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("file_create_params");
+
+        let result = polar.create_file(&params).await;
+
+        assert!(result.is_ok_and(|file| file.upload.parts.len() == 1 && !file.is_uploaded));
+    }
+
+    #[tokio::test]
+    async fn should_not_create_file() {
+        let mock = get_mock("POST", "/files", 422, get_fixture::<Value>("unprocessable_entity")).await;
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("file_create_params");
+
+        let result = polar.create_file(&params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_complete_file_upload() {
+        let file_id = Uuid::new_v4();
         let mock = get_mock(
-            "DELETE",
-            &format!("/subscriptions/{}", subscription_id),
+            "POST",
+            &format!("/files/{}/uploaded", file_id),
             200,
-            get_fixture::<Value>("subscription"),
+            get_fixture::<Value>("file"),
         )
         .await;
-
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.revoke_subscription(subscription_id).await;
+        let params = get_fixture("file_upload_completed_params");
 
-        assert!(result.is_ok());
+        let result = polar.complete_file_upload(file_id, &params).await;
+
+        assert!(result.is_ok_and(|file| file.is_uploaded));
     }
 
     #[tokio::test]
-    async fn should_not_revoke_subscription() {
-        let subscription_id = Uuid::new_v4();
+    async fn should_not_complete_file_upload() {
+        let file_id = Uuid::new_v4();
         let mock = get_mock(
-            "DELETE",
-            &format!("/subscriptions/{}", subscription_id),
+            "POST",
+            &format!("/files/{}/uploaded", file_id),
             422,
             get_fixture::<Value>("unprocessable_entity"),
         )
         .await;
-
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.revoke_subscription(subscription_id).await;
+        let params = get_fixture("file_upload_completed_params");
+
+        let result = polar.complete_file_upload(file_id, &params).await;
 
         assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn should_get_product() {
-        let product_id = Uuid::new_v4();
+    async fn should_update_file() {
+        let file_id = Uuid::new_v4();
         let mock = get_mock(
-            "GET",
-            &format!("/products/{}", product_id),
+            "PATCH",
+            &format!("/files/{}", file_id),
             200,
-            get_fixture::<Value>("product"),
+            get_fixture::<Value>("file"),
         )
         .await;
-
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.get_product(product_id).await;
+        let params = get_fixture("update_file_params");
+
+        let result = polar.update_file(file_id, &params).await;
 
         assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn should_not_get_product() {
-        let product_id = Uuid::new_v4();
+    async fn should_not_update_file() {
+        let file_id = Uuid::new_v4();
         let mock = get_mock(
-            "GET",
-            &format!("/products/{}", product_id),
+            "PATCH",
+            &format!("/files/{}", file_id),
             404,
             get_fixture::<Value>("not_found"),
         )
         .await;
-
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.get_product(product_id).await;
+        let params = get_fixture("update_file_params");
+
+        let result = polar.update_file(file_id, &params).await;
 
         assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn should_list_products() {
-        let mock = get_mock("GET", "/products", 200, get_fixture::<Value>("products_list")).await;
+    async fn should_delete_file() {
+        let file_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("DELETE"))
+            .and(matchers::path(format!("/files/{}", file_id)))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock)
+            .await;
 
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.list_products(&ListProductsParams::default()).await;
+        let result = polar.delete_file(file_id).await;
 
         assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn should_create_product() {
-        let mock = get_mock("POST", "/products", 201, get_fixture::<Value>("product")).await;
+    async fn should_not_delete_file() {
+        let file_id = Uuid::new_v4();
+        let mock = get_mock(
+            "DELETE",
+            &format!("/files/{}", file_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
 
         let polar = get_test_polar(mock.uri());
 
-        let params = get_fixture("product_params");
+        let result = polar.delete_file(file_id).await;
 
-        let result = polar.create_product(&params).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_create_custom_field() {
+        let mock = get_mock("POST", "/custom-fields", 201, get_fixture::<Value>("custom_field")).await;
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("custom_field_params");
+
+        let result = polar.create_custom_field(&params).await;
 
         assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn should_not_create_product() {
-        let mock = get_mock("POST", "/products", 422, get_fixture::<Value>("unprocessable_entity")).await;
-
+    async fn should_not_create_custom_field() {
+        let mock = get_mock(
+            "POST",
+            "/custom-fields",
+            422,
+            get_fixture::<Value>("unprocessable_entity"),
+        )
+        .await;
         let polar = get_test_polar(mock.uri());
 
-        let params = get_fixture("product_params");
+        let params = get_fixture("custom_field_params");
 
-        let result = polar.create_product(&params).await;
+        let result = polar.create_custom_field(&params).await;
 
         assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn should_update_product() {
-        let product_id = Uuid::new_v4();
+    async fn should_update_custom_field() {
+        let custom_field_id = Uuid::new_v4();
         let mock = get_mock(
             "PATCH",
-            &format!("/products/{}", product_id),
+            &format!("/custom-fields/{}", custom_field_id),
             200,
-            get_fixture::<Value>("product"),
+            get_fixture::<Value>("custom_field"),
         )
         .await;
-
         let polar = get_test_polar(mock.uri());
 
-        let params = get_fixture("update_product_params");
+        let params = get_fixture("update_custom_field_params");
 
-        let result = polar.update_product(product_id, &params).await;
+        let result = polar.update_custom_field(custom_field_id, &params).await;
 
         assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn should_not_update_product() {
-        let product_id = Uuid::new_v4();
+    async fn should_not_update_custom_field() {
+        let custom_field_id = Uuid::new_v4();
         let mock = get_mock(
             "PATCH",
-            &format!("/products/{}", product_id),
-            422,
-            get_fixture::<Value>("unprocessable_entity"),
+            &format!("/custom-fields/{}", custom_field_id),
+            404,
+            get_fixture::<Value>("not_found"),
         )
         .await;
-
         let polar = get_test_polar(mock.uri());
 
-        let params = get_fixture("update_product_params");
+        let params = get_fixture("update_custom_field_params");
 
-        let result = polar.update_product(product_id, &params).await;
+        let result = polar.update_custom_field(custom_field_id, &params).await;
 
         assert!(result.is_err());
     }
 
     #[tokio::test]
-    async fn should_update_product_benefits() {
-        let product_id = Uuid::new_v4();
-        let mock = get_mock(
-            "PATCH",
-            &format!("/products/{}/benefits", product_id),
-            200,
-            get_fixture::<Value>("product"),
-        )
-        .await;
+    async fn should_delete_custom_field() {
+        let custom_field_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("DELETE"))
+            .and(matchers::path(format!("/custom-fields/{}", custom_field_id)))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&mock)
+            .await;
 
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.update_product_benefits(product_id, vec![Uuid::new_v4()]).await;
+        let result = polar.delete_custom_field(custom_field_id).await;
 
         assert!(result.is_ok());
     }
 
     #[tokio::test]
-    async fn should_not_update_product_benefits() {
-        let product_id = Uuid::new_v4();
+    async fn should_not_delete_custom_field() {
+        let custom_field_id = Uuid::new_v4();
         let mock = get_mock(
-            "PATCH",
-            &format!("/products/{}/benefits", product_id),
-            422,
-            get_fixture::<Value>("unprocessable_entity"),
+            "DELETE",
+            &format!("/custom-fields/{}", custom_field_id),
+            404,
+            get_fixture::<Value>("not_found"),
         )
         .await;
 
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.update_product_benefits(product_id, vec![Uuid::new_v4()]).await;
+        let result = polar.delete_custom_field(custom_field_id).await;
 
         assert!(result.is_err());
     }
@@ -767,7 +4243,7 @@ mod tests {
         let mock = get_mock("POST", "/events/ingest", 201, get_fixture::<Value>("events_inserted")).await;
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.ingest_events(vec![]).await;
+        let result = polar.ingest_events(&[]).await;
 
         assert!(result.is_ok());
     }
@@ -783,11 +4259,69 @@ mod tests {
         .await;
         let polar = get_test_polar(mock.uri());
 
-        let result = polar.ingest_events(vec![]).await;
+        let result = polar.ingest_events(&[]).await;
 
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn should_not_ingest_events_when_response_is_unexpected() {
+        let mock = get_mock("POST", "/events/ingest", 201, json!({ "inserted": "2" })).await;
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.ingest_events(&[]).await;
+
+        assert!(matches!(result, Err(PolarError::Unknown(_))));
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn should_ingest_events_compressed() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/events/ingest"))
+            .and(matchers::header("content-encoding", "gzip"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(get_fixture::<Value>("events_inserted")))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri()).with_event_compression(0);
+
+        let result = polar.ingest_events(&[]).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn should_ingest_events_uncompressed_when_unsupported() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/events/ingest"))
+            .and(matchers::header("content-encoding", "gzip"))
+            .respond_with(ResponseTemplate::new(415))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/events/ingest"))
+            .and(matchers::body_json(json!({ "events": [] })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(get_fixture::<Value>("events_inserted")))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri()).with_event_compression(0);
+
+        let result = polar.ingest_events(&[]).await;
+
+        assert!(result.is_ok());
+    }
+
     #[tokio::test]
     async fn should_get_event() {
         let event_id = Uuid::new_v4();
@@ -897,6 +4431,44 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[tokio::test]
+    async fn should_list_meters_with_filters() {
+        let organization_ids = [Uuid::new_v4(), Uuid::new_v4()];
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/meters"))
+            .and(matchers::query_param(
+                "organization_id",
+                organization_ids[0].to_string(),
+            ))
+            .and(matchers::query_param(
+                "organization_id",
+                organization_ids[1].to_string(),
+            ))
+            .and(matchers::query_param("is_archived", "false"))
+            .and(matchers::query_param("sorting", "-created_at"))
+            .and(matchers::query_param("sorting", "name"))
+            .and(matchers::query_param("metadata[team]", "growth"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("meters_list")))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .list_meters(&ListMetersParams {
+                organization_id: Some(organization_ids.to_vec()),
+                is_archived: Some(false),
+                sorting: Some(vec![MetersSorting::CreatedAtDesc, MetersSorting::Name]),
+                metadata: Some(HashMap::from([("team".to_owned(), "growth".to_owned())])),
+                ..Default::default()
+            })
+            .await;
+
+        assert!(result.is_ok());
+    }
+
     #[tokio::test]
     async fn should_update_meter() {
         let meter_id = Uuid::new_v4();
@@ -937,6 +4509,47 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn should_archive_meter() {
+        let meter_id = Uuid::new_v4();
+        let mut meter = get_fixture::<Value>("meter");
+
+        meter["archived_at"] = json!("2023-11-07T05:31:56Z");
+
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("PATCH"))
+            .and(matchers::path(format!("/meters/{}", meter_id)))
+            .and(matchers::body_json(json!({ "is_archived": true })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(meter))
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.archive_meter(meter_id).await;
+
+        assert!(result.is_ok_and(|meter| meter.is_archived()));
+    }
+
+    #[tokio::test]
+    async fn should_not_archive_meter() {
+        let meter_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/meters/{}", meter_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.archive_meter(meter_id).await;
+
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn should_get_meter_quantities() {
         let meter_id = Uuid::new_v4();
@@ -976,4 +4589,171 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn should_list_webhook_endpoints() {
+        let mock = get_mock(
+            "GET",
+            "/webhooks/endpoints",
+            200,
+            get_fixture::<Value>("webhook_endpoints_list"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .list_webhook_endpoints(&ListWebhookEndpointsParams::default())
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_create_webhook_endpoint() {
+        let mock = get_mock(
+            "POST",
+            "/webhooks/endpoints",
+            201,
+            get_fixture::<Value>("webhook_endpoint"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("webhook_endpoint_params");
+
+        let result = polar.create_webhook_endpoint(&params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_create_webhook_endpoint() {
+        let mock = get_mock(
+            "POST",
+            "/webhooks/endpoints",
+            422,
+            get_fixture::<Value>("unprocessable_entity"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("webhook_endpoint_params");
+
+        let result = polar.create_webhook_endpoint(&params).await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_update_webhook_endpoint() {
+        let endpoint_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/webhooks/endpoints/{}", endpoint_id),
+            200,
+            get_fixture::<Value>("webhook_endpoint"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_webhook_endpoint_params");
+
+        let result = polar.update_webhook_endpoint(endpoint_id, &params).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_not_update_webhook_endpoint() {
+        let endpoint_id = Uuid::new_v4();
+        let mock = get_mock(
+            "PATCH",
+            &format!("/webhooks/endpoints/{}", endpoint_id),
+            404,
+            get_fixture::<Value>("not_found"),
+        )
+        .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let params = get_fixture("update_webhook_endpoint_params");
+
+        let result = polar.update_webhook_endpoint(endpoint_id, &params).await;
+
+        assert!(result.is_err());
+    }
+
+    async fn get_webhook_endpoints_mock(endpoints: Vec<Value>) -> MockServer {
+        get_mock(
+            "GET",
+            "/webhooks/endpoints",
+            200,
+            json!({
+                "items": endpoints,
+                "pagination": { "total_count": endpoints.len(), "max_page": 1 },
+            }),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn should_ensure_webhook_without_changes() {
+        let endpoint = get_fixture::<Value>("webhook_endpoint");
+        let url = Url::parse(endpoint["url"].as_str().unwrap()).unwrap();
+        let mock = get_webhook_endpoints_mock(vec![endpoint]).await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar
+            .ensure_webhook(&url, &[WebhookEventType::OrderPaid, WebhookEventType::OrderCreated])
+            .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_ensure_webhook_by_updating_events() {
+        let endpoint = get_fixture::<Value>("webhook_endpoint");
+        let url = Url::parse(endpoint["url"].as_str().unwrap()).unwrap();
+        let endpoint_id = endpoint["id"].as_str().unwrap().to_owned();
+        let mock = get_webhook_endpoints_mock(vec![endpoint.clone()]).await;
+
+        Mock::given(matchers::method("PATCH"))
+            .and(matchers::path(format!("/webhooks/endpoints/{}", endpoint_id)))
+            .and(matchers::body_json(json!({ "events": ["order.refunded"] })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(endpoint))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.ensure_webhook(&url, &[WebhookEventType::OrderRefunded]).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_ensure_webhook_by_creating_endpoint() {
+        let endpoint = get_fixture::<Value>("webhook_endpoint");
+        let url = Url::parse(endpoint["url"].as_str().unwrap()).unwrap();
+        let mock = get_webhook_endpoints_mock(vec![]).await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/webhooks/endpoints"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(endpoint))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = get_test_polar(mock.uri());
+
+        let result = polar.ensure_webhook(&url, &[WebhookEventType::OrderPaid]).await;
+
+        assert!(result.is_ok());
+    }
 }
@@ -0,0 +1,304 @@
+use std::ops::Deref;
+
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+
+use crate::{Page, Polar, PolarResult};
+
+/// A page returned by a list method, along with the request that fetched it and the rate limit info of the response.
+///
+/// It dereferences to its [`Page`], and [`ListResponse::next_page`] fetches the following one with the same filters.
+pub struct ListResponse<T> {
+    /// The items and pagination info.
+    pub page: Page<T>,
+    /// Rate limit info, from the response headers.
+    pub rate_limit: RateLimit,
+    path: String,
+    params: Map<String, Value>,
+}
+
+/// Rate limit info of a response. Fields are `None` when the API doesn't send the matching header.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RateLimit {
+    /// Maximum number of requests allowed in the current window.
+    pub limit: Option<u64>,
+    /// Number of requests remaining in the current window.
+    pub remaining: Option<u64>,
+    /// Number of seconds until the current window resets.
+    pub reset: Option<u64>,
+}
+
+impl RateLimit {
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Self {
+        let get = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+        };
+
+        Self {
+            limit: get("x-ratelimit-limit"),
+            remaining: get("x-ratelimit-remaining"),
+            reset: get("x-ratelimit-reset"),
+        }
+    }
+}
+
+impl<T> ListResponse<T> {
+    pub(crate) fn new(page: Page<T>, rate_limit: RateLimit, path: String, params: Map<String, Value>) -> Self {
+        Self {
+            page,
+            rate_limit,
+            path,
+            params,
+        }
+    }
+
+    /// Number of the page, starting at 1.
+    pub fn page_number(&self) -> usize {
+        page_number(&self.params)
+    }
+
+    /// Query params of the request that fetched the page, as sent to the API.
+    pub fn params(&self) -> &Map<String, Value> {
+        &self.params
+    }
+
+    /// Whether there is a page after this one.
+    pub fn has_next_page(&self) -> bool {
+        self.page_number() < self.page.pagination.max_page
+    }
+
+    /// Fetches the page after this one, with the same filters and size, or returns `None` if this is the last page.
+    pub async fn next_page(&self, polar: &Polar) -> PolarResult<Option<ListResponse<T>>>
+    where
+        T: DeserializeOwned,
+    {
+        if !self.has_next_page() {
+            return Ok(None);
+        }
+
+        let mut params = self.params.clone();
+
+        params.insert("page".to_owned(), json!(self.page_number() + 1));
+
+        polar.get_list_with_map(&self.path, params).await.map(Some)
+    }
+
+    /// Drops the request and rate limit info, e.g. to move the items out.
+    pub fn into_page(self) -> Page<T> {
+        self.page
+    }
+
+    /// Fetches the pages after this one, returning the items of all of them.
+    pub(crate) async fn collect_items(self, polar: &Polar) -> PolarResult<Vec<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let mut list = self;
+        let mut items = Vec::new();
+
+        loop {
+            let next = list.next_page(polar).await?;
+
+            items.extend(list.into_page().items);
+
+            match next {
+                Some(next) => list = next,
+                None => return Ok(items),
+            }
+        }
+    }
+
+    /// Returns the first item matching `predicate`, in this page or the following ones, fetched only until it's found.
+    pub(crate) async fn find_item<F>(self, polar: &Polar, mut predicate: F) -> PolarResult<Option<T>>
+    where
+        T: DeserializeOwned,
+        F: FnMut(&T) -> bool,
+    {
+        let mut list = self;
+
+        loop {
+            if let Some(position) = list.page.items.iter().position(&mut predicate) {
+                return Ok(Some(list.into_page().items.swap_remove(position)));
+            }
+
+            match list.next_page(polar).await? {
+                Some(next) => list = next,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Returns the page if one of its items was modified after `since`, or `None`.
+    pub(crate) fn modified_since<F>(self, since: DateTime<Utc>, modified_at: F) -> Option<Self>
+    where
+        F: Fn(&T) -> DateTime<Utc>,
+    {
+        self.page
+            .items
+            .iter()
+            .any(|item| modified_at(item) > since)
+            .then_some(self)
+    }
+}
+
+impl<T> Deref for ListResponse<T> {
+    type Target = Page<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.page
+    }
+}
+
+/// Serializes list params to a map, leaving out the params not set so they're not sent again by
+/// [`ListResponse::next_page`].
+pub(crate) fn params_to_map<P: Serialize>(params: &P) -> PolarResult<Map<String, Value>> {
+    let mut map = match serde_json::to_value(params)? {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    };
+
+    map.retain(|_, value| !value.is_null());
+
+    Ok(map)
+}
+
+fn page_number(params: &Map<String, Value>) -> usize {
+    params
+        .get("page")
+        .and_then(Value::as_u64)
+        .map_or(1, |page| page as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    use super::*;
+    use crate::ListMetersParams;
+    use crate::test_support::get_fixture;
+
+    fn get_page(page: usize, max_page: usize) -> Value {
+        let mut list: Value = get_fixture("meters_list");
+
+        list["pagination"] = json!({ "total_count": max_page, "max_page": max_page });
+        list["items"][0]["name"] = json!(format!("Page {page}"));
+
+        list
+    }
+
+    #[tokio::test]
+    async fn should_fetch_next_page_with_same_filters() {
+        let organization_id = Uuid::new_v4();
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/meters"))
+            .and(matchers::query_param("organization_id", organization_id.to_string()))
+            .and(matchers::query_param("limit", "5"))
+            .and(matchers::query_param_is_missing("page"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("x-ratelimit-remaining", "99")
+                    .set_body_json(get_page(1, 2)),
+            )
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path("/meters"))
+            .and(matchers::query_param("organization_id", organization_id.to_string()))
+            .and(matchers::query_param("limit", "5"))
+            .and(matchers::query_param("page", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_page(2, 2)))
+            .mount(&mock)
+            .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let first = polar
+            .list_meters(&ListMetersParams {
+                organization_id: Some(vec![organization_id]),
+                limit: Some(5),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(first.rate_limit.remaining, Some(99));
+        assert_eq!(first.rate_limit.limit, None);
+
+        let second = first.next_page(&polar).await.unwrap().unwrap();
+
+        assert_eq!(second.page_number(), 2);
+        assert_eq!(second.items[0].name, "Page 2");
+        assert!(second.next_page(&polar).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn should_collect_items_of_every_page() {
+        let mock = MockServer::start().await;
+
+        for page in [1, 2, 3] {
+            Mock::given(matchers::method("GET"))
+                .and(matchers::path("/meters"))
+                .and(matchers::query_param("page", page.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(get_page(page, 3)))
+                .expect(1)
+                .mount(&mock)
+                .await;
+        }
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let first = polar
+            .list_meters(&ListMetersParams {
+                page: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let result = first.collect_items(&polar).await;
+
+        assert!(result.is_ok_and(|meters| {
+            meters
+                .iter()
+                .map(|meter| meter.name.as_str())
+                .eq(["Page 1", "Page 2", "Page 3"])
+        }));
+    }
+
+    #[tokio::test]
+    async fn should_find_item_without_fetching_following_pages() {
+        let mock = MockServer::start().await;
+
+        for (page, expected) in [(1, 1), (2, 1), (3, 0)] {
+            Mock::given(matchers::method("GET"))
+                .and(matchers::path("/meters"))
+                .and(matchers::query_param("page", page.to_string()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(get_page(page, 3)))
+                .expect(expected)
+                .mount(&mock)
+                .await;
+        }
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let first = polar
+            .list_meters(&ListMetersParams {
+                page: Some(1),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let result = first.find_item(&polar, |meter| meter.name == "Page 2").await;
+
+        assert!(result.is_ok_and(|meter| meter.is_some()));
+    }
+}
@@ -1,12 +1,40 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::LazyLock;
 
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use regex::Regex;
+use serde::{Deserialize, Serialize, Serializer};
 use serde_json::Value;
 use url::Url;
 use uuid::Uuid;
 
 use crate::enums::*;
+use crate::{PolarError, PolarResult};
+
+/// Formats of the postal codes of some countries, checked by [`CustomerBillingAddress::normalize`]. Spaces and
+/// dashes are optional where they are commonly left out.
+static POSTAL_CODE_FORMATS: LazyLock<HashMap<&'static str, Regex>> = LazyLock::new(|| {
+    [
+        ("AU", r"^\d{4}$"),
+        ("BR", r"^\d{5}-?\d{3}$"),
+        ("CA", r"^[A-Z]\d[A-Z] ?\d[A-Z]\d$"),
+        ("DE", r"^\d{5}$"),
+        ("ES", r"^\d{5}$"),
+        ("FR", r"^\d{5}$"),
+        ("GB", r"^[A-Z]{1,2}\d[A-Z\d]? ?\d[A-Z]{2}$"),
+        ("IN", r"^\d{6}$"),
+        ("IT", r"^\d{5}$"),
+        ("JP", r"^\d{3}-?\d{4}$"),
+        ("NL", r"^\d{4} ?[A-Z]{2}$"),
+        ("SE", r"^\d{3} ?\d{2}$"),
+        ("US", r"^\d{5}(-\d{4})?$"),
+    ]
+    .into_iter()
+    .map(|(country, format)| (country, Regex::new(format).unwrap()))
+    .collect()
+});
 
 #[derive(Deserialize)]
 pub struct AttachedCustomField {
@@ -26,6 +54,33 @@ pub struct AttachedCustomFieldParams {
     pub custom_field_id: Uuid,
     /// Whether the value is required for this custom field.
     pub required: bool,
+    /// Order of the custom field in the resource, fields without one going last.
+    ///
+    /// The API orders custom fields by their position in the list, so the list is sorted by this value when sent.
+    #[serde(default, skip_serializing)]
+    pub order: Option<usize>,
+}
+
+fn serialize_attached_custom_fields<S: Serializer>(
+    fields: &[AttachedCustomFieldParams],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut fields = fields.iter().collect::<Vec<_>>();
+
+    // Stable sort, fields with the same order keep their position in the list.
+    fields.sort_by_key(|field| field.order.unwrap_or(usize::MAX));
+
+    serializer.collect_seq(fields)
+}
+
+fn serialize_optional_attached_custom_fields<S: Serializer>(
+    fields: &Option<Vec<AttachedCustomFieldParams>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match fields {
+        Some(fields) => serialize_attached_custom_fields(fields, serializer),
+        None => serializer.serialize_none(),
+    }
 }
 
 #[derive(Deserialize)]
@@ -46,6 +101,144 @@ pub struct Benefit {
     pub deletable: bool,
     /// The ID of the organization owning the benefit.
     pub organization_id: Uuid,
+    /// Key-value object storing additional information, empty for public benefits, e.g. in checkouts.
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// Properties specific to the type of benefit, `None` for public benefits. See [`Benefit::typed_properties`].
+    pub properties: Option<Value>,
+}
+
+impl Benefit {
+    /// Decodes the properties according to the type of the benefit.
+    pub fn typed_properties(&self) -> PolarResult<Option<BenefitProperties>> {
+        let Some(properties) = self.properties.clone() else {
+            return Ok(None);
+        };
+
+        let properties = match self.r#type {
+            BenefitType::Custom => BenefitProperties::Custom(serde_json::from_value(properties)?),
+            BenefitType::Discord => BenefitProperties::Discord(serde_json::from_value(properties)?),
+            BenefitType::GithubRepository => BenefitProperties::GithubRepository(serde_json::from_value(properties)?),
+            BenefitType::Downloadables => BenefitProperties::Downloadables(serde_json::from_value(properties)?),
+            BenefitType::LicenseKeys => BenefitProperties::LicenseKeys(serde_json::from_value(properties)?),
+            BenefitType::MeterCredit => BenefitProperties::MeterCredit(serde_json::from_value(properties)?),
+        };
+
+        Ok(Some(properties))
+    }
+}
+
+/// Params to create a benefit, each type of benefit having its own properties.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BenefitCreateParams {
+    Custom(BenefitParams<BenefitCustomProperties>),
+    Discord(BenefitParams<BenefitDiscordProperties>),
+    GithubRepository(BenefitParams<BenefitGitHubRepositoryProperties>),
+    Downloadables(BenefitParams<BenefitDownloadablesProperties>),
+    LicenseKeys(BenefitParams<BenefitLicenseKeysProperties>),
+    MeterCredit(BenefitParams<BenefitMeterCreditProperties>),
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct BenefitCustomProperties {
+    /// Private note to be shared with customers who have this benefit granted.
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct BenefitDiscordProperties {
+    /// Discord bot token used to invite the customers to the server.
+    pub guild_token: String,
+    /// The ID of the Discord role to grant.
+    pub role_id: String,
+    /// Whether to kick the member from the Discord server on revocation.
+    pub kick_member: bool,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct BenefitDownloadablesProperties {
+    /// Files archived by ID. Archived files are kept for customers who already have access to them.
+    pub archived: HashMap<Uuid, bool>,
+    /// List of file IDs to grant access to.
+    ///
+    /// Minimum length: `1`
+    pub files: Vec<Uuid>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct BenefitGitHubRepositoryProperties {
+    /// The owner of the repository.
+    pub repository_owner: String,
+    /// The name of the repository.
+    pub repository_name: String,
+    /// The permission level to grant.
+    pub permission: GitHubRepositoryPermission,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct BenefitLicenseKeyActivationProperties {
+    /// Maximum number of activations per license key.
+    ///
+    /// Required range: `1 <= x <= 50`
+    pub limit: u32,
+    /// Whether customers can manage the activations of their license keys.
+    pub enable_customer_admin: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct BenefitLicenseKeyExpirationProperties {
+    /// Number of timeframes before the license key expires.
+    ///
+    /// Required range: `x >= 1`
+    pub ttl: u32,
+    pub timeframe: LicenseKeyExpirationTimeframe,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct BenefitLicenseKeysProperties {
+    /// Prefix of the generated license keys.
+    pub prefix: Option<String>,
+    /// Expiration of the license keys. If `None`, they never expire.
+    pub expires: Option<BenefitLicenseKeyExpirationProperties>,
+    /// Activation limits of the license keys. If `None`, they don't need to be activated.
+    pub activations: Option<BenefitLicenseKeyActivationProperties>,
+    /// Maximum number of usages of a license key.
+    pub limit_usage: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct BenefitMeterCreditProperties {
+    /// Number of units credited to the meter.
+    pub units: u32,
+    /// Whether unused units are rolled over to the next billing period.
+    pub rollover: bool,
+    /// The ID of the meter to credit.
+    pub meter_id: Uuid,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct BenefitParams<P> {
+    /// The description of the benefit. Will be displayed on products having this benefit.
+    ///
+    /// Required string length: `3 - 42`
+    pub description: String,
+    /// The ID of the organization owning the benefit. **Required unless you use an organization token.**
+    pub organization_id: Option<Uuid>,
+    /// Key-value object allowing you to store additional information.
+    pub metadata: HashMap<String, String>,
+    /// Properties specific to the type of benefit.
+    pub properties: P,
+}
+
+/// Properties of a benefit, by type of benefit.
+pub enum BenefitProperties {
+    Custom(BenefitCustomProperties),
+    Discord(BenefitDiscordProperties),
+    GithubRepository(BenefitGitHubRepositoryProperties),
+    Downloadables(BenefitDownloadablesProperties),
+    LicenseKeys(BenefitLicenseKeysProperties),
+    MeterCredit(BenefitMeterCreditProperties),
 }
 
 #[derive(Deserialize)]
@@ -58,6 +251,47 @@ pub struct BillingAddressFields {
     pub line2: BillingAddressField,
 }
 
+/// Details of the card used for a payment.
+#[derive(Deserialize)]
+pub struct CardPaymentMetadata {
+    /// The brand of the card.
+    pub brand: CardBrand,
+    /// The last 4 digits of the card number.
+    pub last4: String,
+}
+
+#[derive(Deserialize)]
+pub struct CheckoutLink {
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp of the object.
+    pub modified_at: Option<DateTime<Utc>>,
+    /// The ID of the object.
+    pub id: Uuid,
+    pub metadata: HashMap<String, String>,
+    /// Payment processor used.
+    pub payment_processor: PaymentProcessor,
+    /// Client secret used to access the checkout link.
+    pub client_secret: String,
+    /// URL where the customer will be redirected after a successful payment.
+    pub success_url: Option<Url>,
+    /// Optional label to distinguish links internally.
+    pub label: Option<String>,
+    /// Whether to allow the customer to apply discount codes. If you apply a discount through `discount_id`, it'll still be applied, but the customer won't be able to change it.
+    pub allow_discount_codes: bool,
+    /// Whether to require the customer to fill their full billing address, instead of just the country. Customers in the US will always be required to fill their full address, regardless of this setting.
+    pub require_billing_address: bool,
+    /// ID of the discount to apply to the checkout. If the discount is not applicable anymore when opening the checkout link, it'll be ignored.
+    pub discount_id: Option<Uuid>,
+    /// The organization ID.
+    pub organization_id: Uuid,
+    /// List of products available to select.
+    pub products: Vec<CheckoutProduct>,
+    pub discount: Option<Discount>,
+    /// URL where the customer can access the checkout link.
+    pub url: Url,
+}
+
 #[derive(Deserialize)]
 pub struct CheckoutProduct {
     /// Creation timestamp of the object.
@@ -109,15 +343,15 @@ pub struct CheckoutSession {
     /// When checkout is embedded, represents the Origin of the page embedding the checkout. Used as a security measure to send messages only to the embedding page.
     pub embed_origin: Option<String>,
     /// Amount in cents, before discounts and taxes.
-    pub amount: u32,
+    pub amount: i64,
     /// Discount amount in cents.
-    pub discount_amount: u32,
+    pub discount_amount: i64,
     /// Amount in cents, after discounts but before taxes.
-    pub net_amount: u32,
+    pub net_amount: i64,
     /// Sales tax amount in cents. If `null`, it means there is no enough information yet to calculate it.
-    pub tax_amount: Option<u32>,
+    pub tax_amount: Option<i64>,
     /// Amount in cents, after discounts and taxes.
-    pub total_amount: u32,
+    pub total_amount: i64,
     /// Currency code of the checkout session.
     pub currency: String,
     /// ID of the product to checkout.
@@ -186,7 +420,7 @@ pub struct CheckoutSessionParams {
     /// Whether to require the customer to fill their full billing address, instead of just the country. Customers in the US will always be required to fill their full address, regardless of this setting. If you preset the billing address, this setting will be automatically set to `true`.
     pub require_billing_address: bool,
     /// Amount in cents, before discounts and taxes. Only useful for custom prices, it'll be ignored for fixed and free prices.
-    pub amount: Option<u32>,
+    pub amount: Option<i64>,
     /// ID of an existing customer in the organization. The customer data will be pre-filled in the checkout form. The resulting order will be linked to this customer.
     pub customer_id: Option<Uuid>,
     /// Whether the customer is a business or an individual. If `true`, the customer will be required to fill their full billing address and billing name.
@@ -197,7 +431,9 @@ pub struct CheckoutSessionParams {
     pub customer_name: Option<String>,
     /// Email address of the customer.
     pub customer_email: Option<String>,
-    pub customer_ip_address: Option<String>,
+    /// IP address of the customer, IPv4 or IPv6. Used to detect fraud and to compute taxes, so it should be the
+    /// address of the customer's browser, not the one of your server.
+    pub customer_ip_address: Option<IpAddr>,
     pub customer_billing_name: Option<String>,
     /// Billing address of the customer.
     pub customer_billing_address: Option<CustomerBillingAddressParams>,
@@ -212,6 +448,31 @@ pub struct CheckoutSessionParams {
     pub embed_origin: Option<String>,
 }
 
+impl CheckoutSessionParams {
+    /// Prefills the checkout with an existing customer, linking the resulting order to them instead of creating a new
+    /// customer.
+    ///
+    /// The ID, external ID, name, email, billing address and tax ID are copied, the name doubling as billing name.
+    pub fn prefill_from(mut self, customer: &Customer) -> Self {
+        self.customer_id = Some(customer.id);
+        self.external_customer_id = customer.external_id.clone();
+        self.customer_name = customer.name.clone();
+        self.customer_email = Some(customer.email.clone());
+        self.customer_billing_name = customer.name.clone();
+        self.customer_billing_address = customer.billing_address.clone();
+        // The tax ID of a customer comes with its type, e.g. `["FR61954506077", "eu_vat"]`, only the value is sent.
+        self.customer_tax_id = customer.tax_id.as_ref().and_then(|tax_id| tax_id.first().cloned());
+        self
+    }
+
+    /// Normalizes the billing address, if any, see [`CustomerBillingAddress::normalize`].
+    pub fn normalize_billing_address(&mut self) -> PolarResult<()> {
+        self.customer_billing_address
+            .as_mut()
+            .map_or(Ok(()), CustomerBillingAddress::normalize)
+    }
+}
+
 #[derive(Deserialize)]
 pub struct CustomField {
     /// Creation timestamp of the object.
@@ -231,7 +492,58 @@ pub struct CustomField {
     pub properties: CustomFieldProperties,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
+pub struct CustomFieldDateProperties {
+    #[serde(flatten)]
+    pub form: CustomFieldFormProperties,
+    /// Earliest date allowed, as a Unix timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ge: Option<i64>,
+    /// Latest date allowed, as a Unix timestamp.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub le: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CustomFieldFields<P> {
+    /// Identifier of the custom field. It'll be used as key when storing the value. Must be unique across the
+    /// organization. It can only contain ASCII letters, numbers and hyphens.
+    pub slug: String,
+    /// Name of the custom field.
+    pub name: String,
+    /// The ID of the organization owning the custom field. **Required unless you use an organization token.**
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<Uuid>,
+    /// Key-value object allowing you to store additional information.
+    pub metadata: HashMap<String, String>,
+    /// Properties specific to the type of custom field.
+    pub properties: P,
+}
+
+/// How a custom field is displayed in the checkout form, shared by every type of custom field.
+#[derive(Default, Deserialize, Serialize)]
+pub struct CustomFieldFormProperties {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form_label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form_help_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form_placeholder: Option<String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CustomFieldNumberProperties {
+    #[serde(flatten)]
+    pub form: CustomFieldFormProperties,
+    /// Minimum value allowed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ge: Option<i64>,
+    /// Maximum value allowed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub le: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize)]
 pub struct CustomFieldOption {
     /// Minimum length: `1`
     pub value: String,
@@ -239,6 +551,17 @@ pub struct CustomFieldOption {
     pub label: String,
 }
 
+/// Params to create a custom field, each type of custom field having its own properties.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CustomFieldParams {
+    Text(CustomFieldFields<CustomFieldTextProperties>),
+    Number(CustomFieldFields<CustomFieldNumberProperties>),
+    Date(CustomFieldFields<CustomFieldDateProperties>),
+    Checkbox(CustomFieldFields<CustomFieldFormProperties>),
+    Select(CustomFieldFields<CustomFieldSelectProperties>),
+}
+
 #[derive(Deserialize)]
 pub struct CustomFieldProperties {
     /// Minimum length: `1`
@@ -257,6 +580,29 @@ pub struct CustomFieldProperties {
     pub options: Option<Vec<CustomFieldOption>>,
 }
 
+#[derive(Deserialize, Serialize)]
+pub struct CustomFieldSelectProperties {
+    #[serde(flatten)]
+    pub form: CustomFieldFormProperties,
+    /// Minimum length: `1`
+    pub options: Vec<CustomFieldOption>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct CustomFieldTextProperties {
+    #[serde(flatten)]
+    pub form: CustomFieldFormProperties,
+    /// Whether to display a multi-line text area instead of a single-line input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub textarea: Option<bool>,
+    /// Required range: `x >= 0`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<usize>,
+    /// Required range: `x >= 0`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+}
+
 #[derive(Deserialize)]
 pub struct Customer {
     /// The ID of the customer.
@@ -284,31 +630,266 @@ pub struct Customer {
     pub avatar_url: String,
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Clone, Deserialize, Serialize)]
 pub struct CustomerBillingAddress {
     /// Examples: `"US"` `"SE"` `"FR"`
-    country: String,
-    line1: Option<String>,
-    line2: Option<String>,
-    postal_code: Option<String>,
-    city: Option<String>,
-    state: Option<String>,
+    pub country: String,
+    pub line1: Option<String>,
+    pub line2: Option<String>,
+    pub postal_code: Option<String>,
+    pub city: Option<String>,
+    pub state: Option<String>,
+}
+
+impl CustomerBillingAddress {
+    /// Cleans up an address typed by a customer, to avoid `422 Unprocessable Entity` responses: fields are trimmed,
+    /// empty ones are cleared, the country and postal code are uppercased.
+    ///
+    /// Fails with [`PolarError::Request`] if the country isn't a two-letter code, or if the postal code doesn't match
+    /// the format of the country, for the countries whose format is known.
+    pub fn normalize(&mut self) -> PolarResult<()> {
+        fn clean(field: &mut Option<String>) {
+            *field = field
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_owned);
+        }
+
+        self.country = self.country.trim().to_ascii_uppercase();
+
+        if self.country.len() != 2 || !self.country.bytes().all(|byte| byte.is_ascii_uppercase()) {
+            return Err(PolarError::Request(format!("invalid country: {:?}", self.country)));
+        }
+
+        clean(&mut self.line1);
+        clean(&mut self.line2);
+        clean(&mut self.postal_code);
+        clean(&mut self.city);
+        clean(&mut self.state);
+
+        if let Some(postal_code) = &mut self.postal_code {
+            *postal_code = postal_code.to_uppercase();
+
+            if let Some(format) = POSTAL_CODE_FORMATS.get(self.country.as_str())
+                && !format.is_match(postal_code)
+            {
+                return Err(PolarError::Request(format!(
+                    "invalid postal code for {}: {postal_code:?}",
+                    self.country
+                )));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 pub type CustomerBillingAddressParams = CustomerBillingAddress;
 
+#[derive(Deserialize)]
+pub struct CustomerPortalCustomer {
+    /// The ID of the customer.
+    pub id: Uuid,
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp of the object.
+    pub modified_at: Option<DateTime<Utc>>,
+    /// The email address of the customer.
+    pub email: String,
+    /// Whether the customer email address is verified.
+    pub email_verified: bool,
+    /// The name of the customer.
+    pub name: Option<String>,
+    pub billing_address: Option<CustomerBillingAddress>,
+    /// Required array length: 2 elements
+    pub tax_id: Option<Vec<String>>,
+}
+
+/// A customer along with what they currently have access to, as known by Polar.
+#[derive(Deserialize)]
+pub struct CustomerState {
+    #[serde(flatten)]
+    pub customer: Customer,
+    /// The customer's active subscriptions.
+    pub active_subscriptions: Vec<CustomerStateSubscription>,
+    /// The customer's active benefit grants.
+    pub granted_benefits: Vec<CustomerStateBenefitGrant>,
+}
+
+#[derive(Deserialize)]
+pub struct CustomerStateBenefitGrant {
+    /// The ID of the grant.
+    pub id: Uuid,
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp of the object.
+    pub modified_at: Option<DateTime<Utc>>,
+    /// The timestamp when the benefit was granted.
+    pub granted_at: DateTime<Utc>,
+    /// The ID of the benefit concerned by this grant.
+    pub benefit_id: Uuid,
+    /// The type of the benefit concerned by this grant.
+    pub benefit_type: BenefitType,
+}
+
+#[derive(Deserialize)]
+pub struct CustomerStateSubscription {
+    /// The ID of the subscription.
+    pub id: Uuid,
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp of the object.
+    pub modified_at: Option<DateTime<Utc>>,
+    pub metadata: HashMap<String, String>,
+    pub status: SubscriptionStatus,
+    /// The amount of the subscription.
+    pub amount: i64,
+    /// The currency of the subscription.
+    pub currency: String,
+    /// The interval at which the subscription recurs.
+    pub recurring_interval: RecurringInterval,
+    /// The start timestamp of the current billing period.
+    pub current_period_start: DateTime<Utc>,
+    /// The end timestamp of the current billing period.
+    pub current_period_end: Option<DateTime<Utc>>,
+    /// Whether the subscription will be canceled at the end of the current period.
+    pub cancel_at_period_end: bool,
+    /// The timestamp when the subscription was canceled. The subscription might still be active if `cancel_at_period_end` is `true`.
+    pub canceled_at: Option<DateTime<Utc>>,
+    /// The timestamp when the subscription started.
+    pub started_at: Option<DateTime<Utc>>,
+    /// The timestamp when the subscription will end.
+    pub ends_at: Option<DateTime<Utc>>,
+    /// The ID of the subscribed product.
+    pub product_id: Uuid,
+    /// The ID of the applied discount, if any.
+    pub discount_id: Option<Uuid>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct CustomerParams {
+    /// Key-value object allowing you to store additional information.
+    pub metadata: HashMap<String, String>,
+    /// The ID of the customer in your system. This must be unique within the organization. Once set, it can't be updated.
+    pub external_id: Option<String>,
+    /// The email address of the customer. This must be unique within the organization.
+    pub email: String,
+    /// The name of the customer.
+    pub name: Option<String>,
+    pub billing_address: Option<CustomerBillingAddressParams>,
+    /// Required array length: 2 elements
+    pub tax_id: Option<Vec<String>>,
+    /// The ID of the organization owning the customer. **Required unless you use an organization token**.
+    pub organization_id: Option<Uuid>,
+}
+
+impl CustomerParams {
+    /// Normalizes the billing address, if any, see [`CustomerBillingAddress::normalize`].
+    pub fn normalize_billing_address(&mut self) -> PolarResult<()> {
+        self.billing_address
+            .as_mut()
+            .map_or(Ok(()), CustomerBillingAddress::normalize)
+    }
+}
+
 #[derive(Deserialize)]
 pub struct Discount {
     pub duration: DiscountDuration,
     pub duration_in_months: Option<usize>,
     pub r#type: DiscountType,
-    pub amount: Option<u32>,
+    pub amount: Option<i64>,
     pub currency: Option<String>,
     pub basis_points: Option<usize>,
     /// The ID of the object.
     pub id: Uuid,
     pub name: String,
     pub code: Option<String>,
+    /// List of products the discount can be applied to. Only present when the full discount is returned, an empty
+    /// list meaning it applies to every product.
+    pub products: Option<Vec<DiscountProduct>>,
+    /// Timestamp after which the discount is redeemable. Only present when the full discount is returned.
+    pub starts_at: Option<DateTime<Utc>>,
+    /// Timestamp after which the discount is no longer redeemable. Only present when the full discount is returned.
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Maximum number of times the discount can be redeemed. Only present when the full discount is returned.
+    pub max_redemptions: Option<usize>,
+    /// Number of times the discount has been redeemed. Only present when the full discount is returned.
+    pub redemptions_count: Option<usize>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct DiscountParams {
+    /// Key-value object allowing you to store additional information.
+    pub metadata: HashMap<String, String>,
+    /// Name of the discount. Will be displayed to the customer when the discount is applied.
+    pub name: String,
+    /// Code customers can use to apply the discount during checkout. Must be between 3 and 256 characters long and
+    /// contain only alphanumeric characters. If not provided, the discount can only be applied via the API.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Optional timestamp after which the discount is redeemable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starts_at: Option<DateTime<Utc>>,
+    /// Optional timestamp after which the discount is no longer redeemable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Optional maximum number of times the discount can be redeemed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_redemptions: Option<usize>,
+    pub duration: DiscountDuration,
+    /// Number of months the discount should be applied. **Required for `repeating` discounts**.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_in_months: Option<usize>,
+    pub r#type: DiscountType,
+    /// Fixed amount to discount from the invoice total, in cents. **Required for `fixed` discounts**.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<i64>,
+    /// The currency of the fixed amount. **Required for `fixed` discounts**.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Discount percentage in basis points, e.g. `1000` for 10%. **Required for `percentage` discounts**.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub basis_points: Option<usize>,
+    /// List of product IDs the discount can be applied to. It applies to every product if not provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub products: Option<Vec<Uuid>>,
+    /// The ID of the organization owning the discount. **Required unless you use an organization token**.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<Uuid>,
+}
+
+pub type DiscountProduct = OrderProduct;
+
+#[derive(Deserialize)]
+pub struct Downloadable {
+    /// The ID of the object.
+    pub id: Uuid,
+    /// The ID of the benefit granting access to the file.
+    pub benefit_id: Uuid,
+    pub file: DownloadableFile,
+}
+
+#[derive(Deserialize)]
+pub struct DownloadableFile {
+    /// The ID of the object.
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub path: String,
+    pub mime_type: String,
+    pub size: u64,
+    pub storage_version: Option<String>,
+    pub checksum_etag: Option<String>,
+    pub checksum_sha256_base64: Option<String>,
+    pub checksum_sha256_hex: Option<String>,
+    pub last_modified_at: Option<DateTime<Utc>>,
+    /// Signed URL to download the file.
+    pub download: FileDownloadUrl,
+    pub version: Option<String>,
+    pub is_uploaded: bool,
+    pub service: String,
+    pub size_readable: String,
 }
 
 #[derive(Deserialize)]
@@ -333,9 +914,9 @@ pub struct Event {
 }
 
 #[derive(Serialize)]
-pub struct EventParams {
-    /// The name of the event.
-    pub name: String,
+pub struct EventParams<'a> {
+    /// The name of the event. Borrowing it avoids an allocation per event when ingesting large batches.
+    pub name: Cow<'a, str>,
     /// ID of the customer in your Polar organization associated with the event.
     pub customer_id: Option<Uuid>,
     /// Key-value object allowing you to store additional information.
@@ -346,15 +927,236 @@ pub struct EventParams {
     pub organization_id: Option<Uuid>,
 }
 
-#[derive(Default, Serialize)]
-pub struct ListCheckoutSessionsParams {
-    /// Filter by organization ID.
-    pub organization_id: Option<Vec<Uuid>>,
-    /// Filter by product ID.
-    pub product_id: Option<Vec<Uuid>>,
-    /// Filter by customer ID.
-    pub customer_id: Option<Vec<Uuid>>,
-    /// Filter by checkout session status.
+/// Metadata of a file to upload. The file is split into parts, each one being uploaded to its own presigned URL.
+#[derive(Deserialize, Serialize)]
+pub struct FileCreateParams {
+    /// The ID of the organization owning the file. **Required unless you use an organization token.**
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_id: Option<Uuid>,
+    pub name: String,
+    pub mime_type: String,
+    /// Size of the file, in bytes.
+    pub size: u64,
+    /// Base64-encoded SHA-256 checksum of the whole file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum_sha256_base64: Option<String>,
+    pub upload: FileUploadParams,
+    pub service: FileServiceType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct FileDownloadUrl {
+    pub url: Url,
+    /// Headers to send along with the request to the URL.
+    pub headers: HashMap<String, String>,
+    /// Expiration timestamp of the URL.
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A file of the organization, as listed by [`Polar::list_files`](crate::Polar::list_files).
+#[derive(Deserialize)]
+pub struct FileRead {
+    /// The ID of the object.
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub path: String,
+    pub mime_type: String,
+    pub size: u64,
+    pub storage_version: Option<String>,
+    pub checksum_etag: Option<String>,
+    pub checksum_sha256_base64: Option<String>,
+    pub checksum_sha256_hex: Option<String>,
+    pub last_modified_at: Option<DateTime<Utc>>,
+    pub version: Option<String>,
+    pub service: FileServiceType,
+    pub is_uploaded: bool,
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    pub size_readable: String,
+    /// Public URL of the file, only set for product media and organization avatars.
+    #[serde(default)]
+    pub public_url: Option<Url>,
+}
+
+/// A file being uploaded, returned by [`Polar::create_file`](crate::Polar::create_file).
+#[derive(Deserialize)]
+pub struct FileUpload {
+    /// The ID of the object.
+    pub id: Uuid,
+    pub organization_id: Uuid,
+    pub name: String,
+    pub path: String,
+    pub mime_type: String,
+    pub size: u64,
+    pub storage_version: Option<String>,
+    pub checksum_etag: Option<String>,
+    pub checksum_sha256_base64: Option<String>,
+    pub checksum_sha256_hex: Option<String>,
+    pub last_modified_at: Option<DateTime<Utc>>,
+    /// The multipart upload, with a presigned URL per part.
+    pub upload: FileUploadMultipart,
+    pub version: Option<String>,
+    pub is_uploaded: bool,
+    pub service: FileServiceType,
+    pub size_readable: String,
+}
+
+/// The multipart upload of a file, once every part has been uploaded.
+#[derive(Deserialize, Serialize)]
+pub struct FileUploadCompletedParams {
+    /// The ID of the multipart upload, from [`FileUploadMultipart::id`].
+    pub id: String,
+    /// From [`FileUploadMultipart::path`].
+    pub path: String,
+    pub parts: Vec<FileUploadCompletedPart>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct FileUploadCompletedPart {
+    /// Number of the part, starting at 1.
+    pub number: usize,
+    /// Value of the `ETag` header returned when uploading the part.
+    pub checksum_etag: String,
+    /// Base64-encoded SHA-256 checksum of the part.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum_sha256_base64: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct FileUploadMultipart {
+    /// The ID of the multipart upload.
+    pub id: String,
+    pub path: String,
+    pub parts: Vec<FileUploadPart>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct FileUploadParams {
+    pub parts: Vec<FileUploadPartParams>,
+}
+
+#[derive(Deserialize)]
+pub struct FileUploadPart {
+    /// Number of the part, starting at 1.
+    pub number: usize,
+    /// Offset of the first byte of the part in the file.
+    pub chunk_start: u64,
+    /// Offset of the last byte of the part in the file.
+    pub chunk_end: u64,
+    pub checksum_sha256_base64: Option<String>,
+    /// Presigned URL to upload the part to, with a `PUT` request.
+    pub url: Url,
+    /// Expiration timestamp of the URL.
+    pub expires_at: DateTime<Utc>,
+    /// Headers to send along with the request to the URL.
+    pub headers: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct FileUploadPartParams {
+    /// Number of the part, starting at 1.
+    pub number: usize,
+    /// Offset of the first byte of the part in the file.
+    pub chunk_start: u64,
+    /// Offset of the last byte of the part in the file.
+    pub chunk_end: u64,
+    /// Base64-encoded SHA-256 checksum of the part.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum_sha256_base64: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct LicenseKey {
+    /// The ID of the object.
+    pub id: Uuid,
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp of the object.
+    pub modified_at: Option<DateTime<Utc>>,
+    /// The ID of the organization owning the license key.
+    pub organization_id: Uuid,
+    /// The ID of the customer owning the license key.
+    pub customer_id: Uuid,
+    pub customer: Customer,
+    /// The ID of the benefit granting the license key.
+    pub benefit_id: Uuid,
+    /// The license key, to be validated by your software.
+    pub key: String,
+    /// The last characters of the license key, safe to display.
+    pub display_key: String,
+    pub status: LicenseKeyStatus,
+    /// Maximum number of activations, unlimited if `None`.
+    pub limit_activations: Option<usize>,
+    /// Number of times the license key has been used.
+    pub usage: usize,
+    /// Maximum usage of the license key, unlimited if `None`.
+    pub limit_usage: Option<usize>,
+    /// Number of times the license key has been validated.
+    pub validations: usize,
+    /// Timestamp of the last validation.
+    pub last_validated_at: Option<DateTime<Utc>>,
+    /// Expiration timestamp of the license key, it never expires if `None`.
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Activations of the license key. Only present when a single license key is returned.
+    pub activations: Option<Vec<LicenseKeyActivation>>,
+}
+
+#[derive(Deserialize)]
+pub struct LicenseKeyActivation {
+    /// The ID of the object.
+    pub id: Uuid,
+    /// The ID of the activated license key.
+    pub license_key_id: Uuid,
+    /// Label of the activation, e.g. the name of the device.
+    pub label: String,
+    /// Key-value object set when activating, e.g. to identify the device.
+    pub meta: HashMap<String, Value>,
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp of the object.
+    pub modified_at: Option<DateTime<Utc>>,
+    /// The activated license key. Only present when a single activation is returned.
+    pub license_key: Option<LicenseKey>,
+}
+
+#[derive(Default, Serialize)]
+pub struct ListBenefitsParams {
+    /// Filter by organization ID.
+    pub organization_id: Option<Vec<Uuid>>,
+    /// Filter by benefit type.
+    pub type_filter: Option<Vec<BenefitType>>,
+    /// Filter by benefit ID.
+    pub id: Option<Vec<Uuid>>,
+    /// Exclude benefits with these IDs.
+    pub exclude_id: Option<Vec<Uuid>>,
+    /// Filter by description.
+    pub query: Option<String>,
+    /// Page number, defaults to 1.
+    ///
+    /// Required range: `x > 0`
+    pub page: Option<usize>,
+    /// Size of a page, defaults to 10. Maximum is 100.
+    ///
+    /// Required range: `x > 0`
+    pub limit: Option<u8>,
+    /// Sorting criterion. Several criteria can be used simultaneously and will be applied in order. Add a minus sign - before the criteria name to sort by descending order.
+    pub sorting: Option<Vec<BenefitsSorting>>,
+    /// Filter by metadata key-value pairs.
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Default, Serialize)]
+pub struct ListCheckoutSessionsParams {
+    /// Filter by organization ID.
+    pub organization_id: Option<Vec<Uuid>>,
+    /// Filter by product ID.
+    pub product_id: Option<Vec<Uuid>>,
+    /// Filter by customer ID.
+    pub customer_id: Option<Vec<Uuid>>,
+    /// Filter by checkout session status.
     pub status: Option<CheckoutSessionStatus>,
     /// Filter by customer email.
     pub query: Option<String>,
@@ -370,12 +1172,104 @@ pub struct ListCheckoutSessionsParams {
     pub sorting: Option<Vec<CheckoutSessionsSorting>>,
 }
 
+#[derive(Default, Serialize)]
+pub struct ListCustomersParams {
+    /// Filter by organization ID.
+    pub organization_id: Option<Vec<Uuid>>,
+    /// Filter by exact email.
+    pub email: Option<String>,
+    /// Filter by customer external ID.
+    pub external_id: Option<Vec<String>>,
+    /// Filter by name or email.
+    pub query: Option<String>,
+    /// Page number, defaults to 1.
+    ///
+    /// Required range: `x > 0`
+    pub page: Option<usize>,
+    /// Size of a page, defaults to 10. Maximum is 100.
+    ///
+    /// Required range: `x > 0`
+    pub limit: Option<u8>,
+    /// Sorting criterion. Several criteria can be used simultaneously and will be applied in order. Add a minus sign - before the criteria name to sort by descending order.
+    pub sorting: Option<Vec<CustomersSorting>>,
+    /// Filter by metadata key-value pairs.
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Default, Serialize)]
+pub struct ListDiscountsParams {
+    /// Filter by organization ID.
+    pub organization_id: Option<Vec<Uuid>>,
+    /// Filter by name.
+    pub query: Option<String>,
+    /// Page number, defaults to 1.
+    ///
+    /// Required range: `x > 0`
+    pub page: Option<usize>,
+    /// Size of a page, defaults to 10. Maximum is 100.
+    ///
+    /// Required range: `x > 0`
+    pub limit: Option<u8>,
+    /// Sorting criterion. Several criteria can be used simultaneously and will be applied in order. Add a minus sign - before the criteria name to sort by descending order.
+    pub sorting: Option<Vec<DiscountsSorting>>,
+    /// Filter by metadata key-value pairs.
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Default, Serialize)]
+pub struct ListDownloadablesParams {
+    /// Filter by benefit ID.
+    pub benefit_id: Option<Vec<Uuid>>,
+    /// Page number, defaults to 1.
+    ///
+    /// Required range: `x > 0`
+    pub page: Option<usize>,
+    /// Size of a page, defaults to 10. Maximum is 100.
+    ///
+    /// Required range: `x > 0`
+    pub limit: Option<u8>,
+}
+
+#[derive(Default, Serialize)]
+pub struct ListFilesParams {
+    /// Filter by organization ID.
+    pub organization_id: Option<Vec<Uuid>>,
+    /// Filter by file ID.
+    pub ids: Option<Vec<Uuid>>,
+    /// Page number, defaults to 1.
+    ///
+    /// Required range: `x > 0`
+    pub page: Option<usize>,
+    /// Size of a page, defaults to 10. Maximum is 100.
+    ///
+    /// Required range: `x > 0`
+    pub limit: Option<u8>,
+}
+
+#[derive(Default, Serialize)]
+pub struct ListLicenseKeysParams {
+    /// Filter by organization ID.
+    pub organization_id: Option<Vec<Uuid>>,
+    /// Filter by benefit ID.
+    pub benefit_id: Option<Vec<Uuid>>,
+    /// Page number, defaults to 1.
+    ///
+    /// Required range: `x > 0`
+    pub page: Option<usize>,
+    /// Size of a page, defaults to 10. Maximum is 100.
+    ///
+    /// Required range: `x > 0`
+    pub limit: Option<u8>,
+}
+
 #[derive(Default, Serialize)]
 pub struct ListMetersParams {
     /// Filter by organization ID.
     pub organization_id: Option<Vec<Uuid>>,
     /// Filter by name.
     pub query: Option<String>,
+    /// Filter on archived meters.
+    pub is_archived: Option<bool>,
     /// Page number, defaults to 1.
     ///
     /// Required range: `x > 0`
@@ -390,6 +1284,56 @@ pub struct ListMetersParams {
     pub metadata: Option<HashMap<String, String>>,
 }
 
+#[derive(Default, Serialize)]
+pub struct ListOrdersParams {
+    /// Filter by organization ID.
+    pub organization_id: Option<Vec<Uuid>>,
+    /// Filter by product ID.
+    pub product_id: Option<Vec<Uuid>>,
+    /// Filter by discount ID.
+    pub discount_id: Option<Vec<Uuid>>,
+    /// Filter by customer ID.
+    pub customer_id: Option<Vec<Uuid>>,
+    /// Filter by checkout ID.
+    pub checkout_id: Option<Vec<Uuid>>,
+    /// Page number, defaults to 1.
+    ///
+    /// Required range: `x > 0`
+    pub page: Option<usize>,
+    /// Size of a page, defaults to 10. Maximum is 100.
+    ///
+    /// Required range: `x > 0`
+    pub limit: Option<u8>,
+    /// Sorting criterion. Several criteria can be used simultaneously and will be applied in order. Add a minus sign - before the criteria name to sort by descending order.
+    pub sorting: Option<Vec<OrdersSorting>>,
+    /// Filter by metadata key-value pairs.
+    pub metadata: Option<HashMap<String, String>>,
+}
+
+#[derive(Default, Serialize)]
+pub struct ListPaymentsParams {
+    /// Filter by organization ID.
+    pub organization_id: Option<Vec<Uuid>>,
+    /// Filter by checkout ID.
+    pub checkout_id: Option<Vec<Uuid>>,
+    /// Filter by order ID.
+    pub order_id: Option<Vec<Uuid>>,
+    /// Filter by payment status.
+    pub status: Option<Vec<PaymentStatus>>,
+    /// Filter by payment method, e.g. `card`.
+    pub method: Option<Vec<String>>,
+    /// Filter by customer email.
+    pub customer_email: Option<Vec<String>>,
+    /// Page number, defaults to 1.
+    ///
+    /// Required range: `x > 0`
+    pub page: Option<usize>,
+    /// Size of a page, defaults to 10. Maximum is 100.
+    ///
+    /// Required range: `x > 0`
+    pub limit: Option<u8>,
+}
+
 #[derive(Default, Serialize)]
 pub struct ListProductsParams {
     /// Filter by product ID.
@@ -418,6 +1362,17 @@ pub struct ListProductsParams {
     pub metadata: Option<HashMap<String, String>>,
 }
 
+impl ListProductsParams {
+    /// Filters on one-time or recurring products.
+    ///
+    /// The API doesn't filter on the recurring interval, use [`Product::billing_type`] on the returned products to
+    /// tell monthly and yearly products apart.
+    pub fn billing_type(mut self, billing_type: ProductBillingType) -> Self {
+        self.is_recurring = Some(billing_type.is_recurring());
+        self
+    }
+}
+
 #[derive(Default, Serialize)]
 pub struct ListSubscriptionsParams {
     /// Filter by organization ID.
@@ -446,6 +1401,20 @@ pub struct ListSubscriptionsParams {
     pub metadata: Option<HashMap<String, String>>,
 }
 
+#[derive(Default, Serialize)]
+pub struct ListWebhookEndpointsParams {
+    /// Filter by organization ID.
+    pub organization_id: Option<Vec<Uuid>>,
+    /// Page number, defaults to 1.
+    ///
+    /// Required range: `x > 0`
+    pub page: Option<usize>,
+    /// Size of a page, defaults to 10. Maximum is 100.
+    ///
+    /// Required range: `x > 0`
+    pub limit: Option<u8>,
+}
+
 #[derive(Deserialize)]
 pub struct Media {
     /// The ID of the object.
@@ -485,6 +1454,15 @@ pub struct Meter {
     pub aggregation: MeterAggregation,
     /// The ID of the organization owning the meter.
     pub organization_id: Uuid,
+    /// Archive timestamp of the meter, if archived.
+    pub archived_at: Option<DateTime<Utc>>,
+}
+
+impl Meter {
+    /// Whether the meter is archived, i.e. not used for billing anymore.
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
 }
 
 #[derive(Deserialize, Serialize)]
@@ -548,11 +1526,152 @@ pub struct MeterQuantitiesParams {
 }
 
 #[derive(Deserialize)]
-pub struct MeterQuantity {
-    /// The timestamp for the current period.
-    pub timestamp: DateTime<Utc>,
-    /// The quantity for the current period.
-    pub quantity: usize,
+pub struct MeterQuantity {
+    /// The timestamp for the current period.
+    pub timestamp: DateTime<Utc>,
+    /// The quantity for the current period.
+    pub quantity: usize,
+}
+
+#[derive(Deserialize)]
+pub struct Order {
+    /// The ID of the object.
+    pub id: Uuid,
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp of the object.
+    pub modified_at: Option<DateTime<Utc>>,
+    pub status: OrderStatus,
+    /// Whether the order has been paid for.
+    pub paid: bool,
+    /// Amount in cents, before discounts and taxes.
+    pub subtotal_amount: i64,
+    /// Discount amount in cents.
+    pub discount_amount: i64,
+    /// Amount in cents, after discounts but before taxes.
+    pub net_amount: i64,
+    /// Sales tax amount in cents.
+    pub tax_amount: i64,
+    /// Amount in cents, after discounts and taxes.
+    pub total_amount: i64,
+    /// Amount refunded in cents.
+    pub refunded_amount: i64,
+    /// Sales tax refunded in cents.
+    pub refunded_tax_amount: i64,
+    pub currency: String,
+    pub billing_reason: OrderBillingReason,
+    /// The name of the customer that should appear on the invoice.
+    pub billing_name: Option<String>,
+    pub billing_address: Option<CustomerBillingAddress>,
+    /// The invoice number associated with this order.
+    pub invoice_number: String,
+    /// Whether an invoice has been generated for this order.
+    pub is_invoice_generated: bool,
+    pub customer_id: Uuid,
+    pub product_id: Uuid,
+    pub discount_id: Option<Uuid>,
+    pub subscription_id: Option<Uuid>,
+    pub checkout_id: Option<Uuid>,
+    pub metadata: HashMap<String, String>,
+    /// Key-value object storing custom field values.
+    pub custom_field_data: HashMap<String, Option<String>>,
+    pub customer: Customer,
+    pub product: OrderProduct,
+    pub discount: Option<Discount>,
+    pub subscription: Option<OrderSubscription>,
+    /// Line items composing the order.
+    pub items: Vec<OrderItem>,
+}
+
+#[derive(Deserialize)]
+pub struct OrderInvoice {
+    /// The URL to the invoice.
+    pub url: Url,
+}
+
+#[derive(Deserialize)]
+pub struct OrderItem {
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp of the object.
+    pub modified_at: Option<DateTime<Utc>>,
+    /// The ID of the object.
+    pub id: Uuid,
+    /// Description of the line item charge.
+    pub label: String,
+    /// Amount in cents, before discounts and taxes.
+    pub amount: i64,
+    /// Sales tax amount in cents.
+    pub tax_amount: i64,
+    /// Whether this charge is due to a proration.
+    pub proration: bool,
+    /// Associated price ID, if any.
+    pub product_price_id: Option<Uuid>,
+}
+
+#[derive(Deserialize)]
+pub struct OrderProduct {
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp of the object.
+    pub modified_at: Option<DateTime<Utc>>,
+    /// The ID of the product.
+    pub id: Uuid,
+    /// The name of the product.
+    pub name: String,
+    /// The description of the product.
+    pub description: Option<String>,
+    /// The recurring interval of the product. If `None`, the product is a one-time purchase.
+    pub recurring_interval: Option<RecurringInterval>,
+    /// Whether the product is a subscription.
+    pub is_recurring: bool,
+    /// Whether the product is archived and no longer available.
+    pub is_archived: bool,
+    /// The ID of the organization owning the product.
+    pub organization_id: Uuid,
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+pub struct OrderSubscription {
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp of the object.
+    pub modified_at: Option<DateTime<Utc>>,
+    /// The ID of the object.
+    pub id: Uuid,
+    /// The amount of the subscription.
+    pub amount: i64,
+    /// The currency of the subscription.
+    pub currency: String,
+    /// The interval at which the subscription recurs.
+    pub recurring_interval: RecurringInterval,
+    /// The status of the subscription.
+    pub status: SubscriptionStatus,
+    /// The start timestamp of the current billing period.
+    pub current_period_start: DateTime<Utc>,
+    /// The end timestamp of the current billing period.
+    pub current_period_end: Option<DateTime<Utc>>,
+    /// Whether the subscription will be canceled at the end of the current period.
+    pub cancel_at_period_end: bool,
+    /// The timestamp when the subscription was canceled. The subscription might still be active if `cancel_at_period_end` is `true`.
+    pub canceled_at: Option<DateTime<Utc>>,
+    /// The timestamp when the subscription started.
+    pub started_at: Option<DateTime<Utc>>,
+    /// The timestamp when the subscription will end.
+    pub ends_at: Option<DateTime<Utc>>,
+    /// The timestamp when the subscription ended.
+    pub ended_at: Option<DateTime<Utc>>,
+    /// The ID of the subscribed customer.
+    pub customer_id: Uuid,
+    /// The ID of the subscribed product.
+    pub product_id: Uuid,
+    /// The ID of the applied discount, if any.
+    pub discount_id: Option<Uuid>,
+    pub checkout_id: Option<Uuid>,
+    pub customer_cancellation_reason: Option<CustomerCancellationReason>,
+    pub customer_cancellation_comment: Option<String>,
+    pub metadata: HashMap<String, String>,
 }
 
 #[derive(Deserialize)]
@@ -567,6 +1686,52 @@ pub struct Pagination {
     pub max_page: usize,
 }
 
+#[derive(Deserialize)]
+pub struct Payment {
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp of the object.
+    pub modified_at: Option<DateTime<Utc>>,
+    /// The ID of the object.
+    pub id: Uuid,
+    /// The payment processor.
+    pub processor: PaymentProcessor,
+    /// The payment status.
+    pub status: PaymentStatus,
+    /// The payment amount in cents.
+    pub amount: i64,
+    /// The payment currency. Currently, only `usd` is supported.
+    pub currency: String,
+    /// The payment method used, e.g. `card`.
+    pub method: String,
+    /// Error code, if the payment was declined.
+    pub decline_reason: Option<PaymentDeclineReason>,
+    /// Human-readable error message, if the payment was declined.
+    pub decline_message: Option<String>,
+    /// The ID of the organization that owns the payment.
+    pub organization_id: Uuid,
+    /// The ID of the checkout session associated with this payment.
+    pub checkout_id: Option<Uuid>,
+    /// The ID of the order associated with this payment.
+    pub order_id: Option<Uuid>,
+    /// Details of the payment method, their shape depending on `method`. See [`Payment::card_metadata`] for cards.
+    #[serde(default)]
+    pub method_metadata: Option<Value>,
+}
+
+impl Payment {
+    /// Details of the card, for card payments. `None` for other payment methods.
+    pub fn card_metadata(&self) -> Option<CardPaymentMetadata> {
+        if self.method != "card" {
+            return None;
+        }
+
+        self.method_metadata
+            .as_ref()
+            .and_then(|metadata| CardPaymentMetadata::deserialize(metadata).ok())
+    }
+}
+
 #[derive(Deserialize)]
 pub struct Price {
     /// Creation timestamp of the object.
@@ -581,20 +1746,22 @@ pub struct Price {
     /// The ID of the product owning the price.
     pub product_id: Uuid,
     pub r#type: PriceType,
+    /// The recurring interval of the price. Only for legacy prices, otherwise the product one applies.
+    pub recurring_interval: Option<RecurringInterval>,
     /// The currency. Not required for `amount_type: Free`.
     pub price_currency: Option<String>,
     /// The price in cents.  Only for `amount_type: Fixed`.
-    pub price_amount: Option<u32>,
+    pub price_amount: Option<i64>,
     /// The minimum amount the customer can pay. Only for `amount_type: Custom`.
-    pub minimum_amount: Option<u32>,
+    pub minimum_amount: Option<i64>,
     /// The maximum amount the customer can pay. Only for `amount_type: Custom`.
-    pub maximum_amount: Option<u32>,
+    pub maximum_amount: Option<i64>,
     /// The initial amount shown to the customer. Only for `amount_type: Custom`.
-    pub preset_amount: Option<u32>,
+    pub preset_amount: Option<i64>,
     /// The price per unit in cents. Only for `amount_type: MeteredUnit`.
     pub unit_amount: Option<String>,
     /// The maximum amount in cents that can be charged, regardless of the number of units consumed. Only for `amount_type: MeteredUnit`.
-    pub cap_amount: Option<u32>,
+    pub cap_amount: Option<i64>,
     /// The ID of the meter associated to the price. Only for `amount_type: MeteredUnit`.
     pub meter_id: Option<Uuid>,
     /// The meter associated to the price. Only for `amount_type: MeteredUnit`.
@@ -615,19 +1782,19 @@ pub struct PriceParams {
     /// The currency. Not required for `amount_type: Free`.
     pub price_currency: Option<String>,
     /// The price in cents.  Only for `amount_type: Fixed`.
-    pub price_amount: Option<u32>,
+    pub price_amount: Option<i64>,
     /// The minimum amount the customer can pay. Only for `amount_type: Custom`.
-    pub minimum_amount: Option<u32>,
+    pub minimum_amount: Option<i64>,
     /// The maximum amount the customer can pay. Only for `amount_type: Custom`.
-    pub maximum_amount: Option<u32>,
+    pub maximum_amount: Option<i64>,
     /// The initial amount shown to the customer. Only for `amount_type: Custom`.
-    pub preset_amount: Option<u32>,
+    pub preset_amount: Option<i64>,
     /// The ID of the meter associated to the price. Only for `amount_type: MeteredUnit`.
     pub meter_id: Option<Uuid>,
     /// The price per unit in cents. Only for `amount_type: MeteredUnit`.
     pub unit_amount: Option<String>,
     /// The maximum amount in cents that can be charged, regardless of the number of units consumed. Only for `amount_type: MeteredUnit`.
-    pub cap_amount: Option<u32>,
+    pub cap_amount: Option<i64>,
 }
 
 #[derive(Deserialize)]
@@ -661,6 +1828,51 @@ pub struct Product {
     pub attached_custom_fields: Vec<AttachedCustomField>,
 }
 
+impl Product {
+    /// How the product is billed.
+    ///
+    /// Legacy recurring products have no interval of their own, the one of their first recurring price is used.
+    pub fn billing_type(&self) -> ProductBillingType {
+        match self.recurring_interval {
+            Some(interval) => ProductBillingType::Recurring(interval),
+            None if self.is_recurring => self
+                .prices
+                .iter()
+                .find_map(|price| price.recurring_interval)
+                .map_or(ProductBillingType::OneTime, ProductBillingType::Recurring),
+            None => ProductBillingType::OneTime,
+        }
+    }
+
+    /// Selects the static price (fixed, custom or free) to checkout this product in the given currency and interval.
+    ///
+    /// Archived and metered prices are ignored, and a `recurring_interval` of `None` means a one-time purchase. A price
+    /// in the requested currency is preferred over a free one, then the oldest price wins, so the selection doesn't
+    /// depend on the order returned by the API.
+    pub fn price_for(&self, currency: &str, recurring_interval: Option<RecurringInterval>) -> Option<&Price> {
+        self.prices
+            .iter()
+            .filter(|price| !price.is_archived && !matches!(price.amount_type, AmountType::MeteredUnit))
+            .filter(|price| {
+                price.recurring_interval.as_ref().or(self.recurring_interval.as_ref()) == recurring_interval.as_ref()
+            })
+            .filter(|price| {
+                matches!(price.amount_type, AmountType::Free)
+                    || price
+                        .price_currency
+                        .as_ref()
+                        .is_some_and(|price_currency| price_currency.eq_ignore_ascii_case(currency))
+            })
+            .min_by_key(|price| {
+                (
+                    matches!(price.amount_type, AmountType::Free),
+                    price.created_at,
+                    price.id,
+                )
+            })
+    }
+}
+
 #[derive(Default, Deserialize, Serialize)]
 pub struct ProductParams {
     /// The name of the product.
@@ -678,6 +1890,7 @@ pub struct ProductParams {
     /// List of file IDs. Each one must be on the same organization as the product, of type `product_media` and correctly uploaded.
     pub medias: Option<Vec<Uuid>>,
     /// List of custom fields to attach.
+    #[serde(serialize_with = "serialize_attached_custom_fields")]
     pub attached_custom_fields: Vec<AttachedCustomFieldParams>,
     /// The ID of the organization owning the product. **Required unless you use an organization token**.
     pub organization_id: Option<Uuid>,
@@ -692,7 +1905,7 @@ pub struct Subscription {
     /// The ID of the object.
     pub id: Uuid,
     /// The amount of the subscription.
-    pub amount: u32,
+    pub amount: i64,
     /// The currency of the subscription.
     pub currency: String,
     /// The interval at which the subscription recurs.
@@ -748,7 +1961,7 @@ pub struct SubscriptionMeter {
     /// The number of credited units so far in this billing period.
     pub credited_units: usize,
     /// The amount due in cents so far in this billing period.
-    pub amount: u32,
+    pub amount: i64,
     /// The ID of the meter.
     pub meter_id: Uuid,
     /// The meter associated with this subscription.
@@ -777,6 +1990,198 @@ pub struct SubscriptionParams {
     pub revoke: Option<bool>,
 }
 
+/// Params to update a benefit, the type being the one of the benefit.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UpdateBenefitParams {
+    Custom(UpdateBenefitFields<BenefitCustomProperties>),
+    Discord(UpdateBenefitFields<BenefitDiscordProperties>),
+    GithubRepository(UpdateBenefitFields<BenefitGitHubRepositoryProperties>),
+    Downloadables(UpdateBenefitFields<BenefitDownloadablesProperties>),
+    LicenseKeys(UpdateBenefitFields<BenefitLicenseKeysProperties>),
+    MeterCredit(UpdateBenefitFields<BenefitMeterCreditProperties>),
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct UpdateBenefitFields<P> {
+    /// The description of the benefit. Will be displayed on products having this benefit.
+    ///
+    /// Required string length: `3 - 42`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Key-value object allowing you to store additional information.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+    /// Properties specific to the type of benefit. They replace the current ones as a whole.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<P>,
+}
+
+/// Fields left as `None` are not sent, so they're kept as is. Nullable fields are wrapped in a second `Option`, set
+/// them to `Some(None)` to clear them.
+#[derive(Default, Deserialize, Serialize)]
+pub struct UpdateCheckoutLinkParams {
+    /// Key-value object allowing you to store additional information.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+    /// List of products that will be available to select at checkout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub products: Option<Vec<Uuid>>,
+    /// Optional label to distinguish links internally.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<Option<String>>,
+    /// Whether to allow the customer to apply discount codes. If you apply a discount through `discount_id`, it'll still be applied, but the customer won't be able to change it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_discount_codes: Option<bool>,
+    /// Whether to require the customer to fill their full billing address, instead of just the country. Customers in the US will always be required to fill their full address, regardless of this setting.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_billing_address: Option<bool>,
+    /// ID of the discount to apply to the checkout. If the discount is not applicable anymore when opening the checkout link, it'll be ignored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discount_id: Option<Option<Uuid>>,
+    /// URL where the customer will be redirected after a successful payment. You can add the `checkout_id={CHECKOUT_ID}` query parameter to retrieve the checkout session id.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success_url: Option<Option<Url>>,
+}
+
+/// Params to update a custom field, the type being the one of the custom field.
+#[derive(Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UpdateCustomFieldParams {
+    Text(UpdateCustomFieldFields<CustomFieldTextProperties>),
+    Number(UpdateCustomFieldFields<CustomFieldNumberProperties>),
+    Date(UpdateCustomFieldFields<CustomFieldDateProperties>),
+    Checkbox(UpdateCustomFieldFields<CustomFieldFormProperties>),
+    Select(UpdateCustomFieldFields<CustomFieldSelectProperties>),
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct UpdateCustomFieldFields<P> {
+    /// Identifier of the custom field. It can only contain ASCII letters, numbers and hyphens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    /// Name of the custom field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Key-value object allowing you to store additional information.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+    /// Properties specific to the type of custom field. They replace the current ones as a whole, e.g. the options of
+    /// a select field.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<P>,
+}
+
+/// Fields left as `None` are not sent, so they're kept as is. Nullable fields are wrapped in a second `Option`, set
+/// them to `Some(None)` to clear them.
+#[derive(Default, Deserialize, Serialize)]
+pub struct UpdateCustomerParams {
+    /// Key-value object allowing you to store additional information.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+    /// The email address of the customer. This must be unique within the organization.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    /// The name of the customer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billing_address: Option<Option<CustomerBillingAddressParams>>,
+    /// Required array length: 2 elements
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tax_id: Option<Option<Vec<String>>>,
+    /// The ID of the customer in your system. This must be unique within the organization. Once set, it can't be updated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+}
+
+impl UpdateCustomerParams {
+    /// Normalizes the billing address, if it's being set, see [`CustomerBillingAddress::normalize`].
+    pub fn normalize_billing_address(&mut self) -> PolarResult<()> {
+        self.billing_address
+            .as_mut()
+            .and_then(Option::as_mut)
+            .map_or(Ok(()), CustomerBillingAddress::normalize)
+    }
+}
+
+/// Fields left as `None` are not sent, so they're kept as is. Nullable fields are wrapped in a second `Option`, set
+/// them to `Some(None)` to clear them.
+#[derive(Default, Deserialize, Serialize)]
+pub struct UpdateDiscountParams {
+    /// Key-value object allowing you to store additional information.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+    /// Name of the discount. Will be displayed to the customer when the discount is applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Code customers can use to apply the discount during checkout.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<Option<String>>,
+    /// Timestamp after which the discount is redeemable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starts_at: Option<Option<DateTime<Utc>>>,
+    /// Timestamp after which the discount is no longer redeemable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ends_at: Option<Option<DateTime<Utc>>>,
+    /// Maximum number of times the discount can be redeemed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_redemptions: Option<Option<usize>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<DiscountDuration>,
+    /// Number of months the discount should be applied, for `repeating` discounts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_in_months: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<DiscountType>,
+    /// Fixed amount to discount from the invoice total, in cents, for `fixed` discounts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<i64>,
+    /// The currency of the fixed amount, for `fixed` discounts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
+    /// Discount percentage in basis points, e.g. `1000` for 10%, for `percentage` discounts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub basis_points: Option<usize>,
+    /// List of product IDs the discount can be applied to, it applies to every product when cleared.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub products: Option<Option<Vec<Uuid>>>,
+}
+
+/// Fields left as `None` are not sent, so they're kept as is. Nullable fields are wrapped in a second `Option`, set
+/// them to `Some(None)` to clear them.
+#[derive(Default, Deserialize, Serialize)]
+pub struct UpdateFileParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<Option<String>>,
+}
+
+/// Fields left as `None` are not sent, so they're kept as is. Nullable fields are wrapped in a second `Option`, set
+/// them to `Some(None)` to clear them.
+#[derive(Default, Deserialize, Serialize)]
+pub struct UpdateLicenseKeyParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<LicenseKeyStatus>,
+    /// Number of times the license key has been used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<usize>,
+    /// Maximum number of activations, unlimited when cleared.
+    ///
+    /// Required range: `0 < x <= 50`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_activations: Option<Option<usize>>,
+    /// Maximum usage of the license key, unlimited when cleared.
+    ///
+    /// Required range: `x > 0`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_usage: Option<Option<usize>>,
+    /// Expiration timestamp of the license key, it never expires when cleared.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<Option<DateTime<Utc>>>,
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct UpdateMeterParams {
     /// Key-value object allowing you to store additional information.
@@ -789,6 +2194,19 @@ pub struct UpdateMeterParams {
     pub filter: Option<MeterFilter>,
     /// The aggregation to apply on the filtered events to calculate the meter.
     pub aggregation: Option<MeterAggregation>,
+    /// Whether the meter is archived. Archived meters are no longer used for billing.
+    pub is_archived: Option<bool>,
+}
+
+/// Fields left as `None` are not sent, so they're kept as is.
+#[derive(Default, Deserialize, Serialize)]
+pub struct UpdateOrderParams {
+    /// The name of the customer that should appear on the invoice.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billing_name: Option<String>,
+    /// The address of the customer that should appear on the invoice. Country and state fields cannot be updated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub billing_address: Option<CustomerBillingAddressParams>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -799,19 +2217,25 @@ pub struct UpdatePriceParams {
     /// The currency. Not required for `amount_type: Free`.
     pub price_currency: Option<String>,
     /// The price in cents.  Only for `amount_type: Fixed`.
-    pub price_amount: Option<u32>,
+    pub price_amount: Option<i64>,
     /// The minimum amount the customer can pay. Only for `amount_type: Custom`.
-    pub minimum_amount: Option<u32>,
+    pub minimum_amount: Option<i64>,
     /// The maximum amount the customer can pay. Only for `amount_type: Custom`.
-    pub maximum_amount: Option<u32>,
+    pub maximum_amount: Option<i64>,
     /// The initial amount shown to the customer. Only for `amount_type: Custom`.
-    pub preset_amount: Option<u32>,
+    pub preset_amount: Option<i64>,
     /// The ID of the meter associated to the price. Only for `amount_type: MeteredUnit`.
     pub meter_id: Option<Uuid>,
     /// The price per unit in cents. Only for `amount_type: MeteredUnit`.
     pub unit_amount: Option<String>,
     /// The maximum amount in cents that can be charged, regardless of the number of units consumed. Only for `amount_type: MeteredUnit`.
-    pub cap_amount: Option<u32>,
+    pub cap_amount: Option<i64>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct UpdateProductBenefitsParams {
+    /// List of benefit IDs. Each one must be on the same organization as the product.
+    pub benefits: Vec<Uuid>,
 }
 
 #[derive(Default, Deserialize, Serialize)]
@@ -833,5 +2257,549 @@ pub struct UpdateProductParams {
     /// List of file IDs. Each one must be on the same organization as the product, of type `product_media` and correctly uploaded.
     pub medias: Option<Vec<Uuid>>,
     /// List of custom fields to attach.
+    #[serde(serialize_with = "serialize_optional_attached_custom_fields")]
     pub attached_custom_fields: Option<Vec<AttachedCustomFieldParams>>,
 }
+
+#[derive(Default, Deserialize, Serialize)]
+pub struct UpdateWebhookEndpointParams {
+    /// The URL where the webhook events will be sent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<Url>,
+    /// The format of the webhook payload.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<WebhookFormat>,
+    /// The events that will trigger the webhook.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<WebhookEventType>>,
+}
+
+#[derive(Deserialize)]
+pub struct WebhookEndpoint {
+    /// Creation timestamp of the object.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp of the object.
+    pub modified_at: Option<DateTime<Utc>>,
+    /// The ID of the object.
+    pub id: Uuid,
+    /// The URL where the webhook events will be sent.
+    pub url: Url,
+    /// The format of the webhook payload.
+    pub format: WebhookFormat,
+    /// The secret used to sign the webhook events.
+    pub secret: String,
+    /// The organization ID associated with the webhook endpoint.
+    pub organization_id: Uuid,
+    /// The events that will trigger the webhook.
+    pub events: Vec<WebhookEventType>,
+}
+
+impl WebhookEndpoint {
+    /// Compares the events of the endpoint with the desired ones, regardless of their order.
+    pub fn events_diff(&self, events: &[WebhookEventType]) -> WebhookEventsDiff {
+        let mut diff = WebhookEventsDiff::default();
+
+        for event in events {
+            if !self.events.contains(event) && !diff.added.contains(event) {
+                diff.added.push(*event);
+            }
+        }
+
+        for event in &self.events {
+            if !events.contains(event) && !diff.removed.contains(event) {
+                diff.removed.push(*event);
+            }
+        }
+
+        diff
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct WebhookEndpointParams {
+    /// The URL where the webhook events will be sent.
+    pub url: Url,
+    /// The format of the webhook payload.
+    pub format: WebhookFormat,
+    /// The events that will trigger the webhook.
+    pub events: Vec<WebhookEventType>,
+    /// The organization ID associated with the webhook endpoint. **Required unless you use an organization token.**
+    pub organization_id: Option<Uuid>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct WebhookEventsDiff {
+    /// Events missing from the endpoint.
+    pub added: Vec<WebhookEventType>,
+    /// Events of the endpoint that are not wanted anymore.
+    pub removed: Vec<WebhookEventType>,
+}
+
+impl WebhookEventsDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, from_value, json};
+
+    use super::*;
+    use crate::test_support::get_fixture;
+
+    /// Asserts that params read from a fixture serialize to the exact JSON of their snapshot in `fixtures/snapshots`,
+    /// i.e. the body sent to the API.
+    ///
+    /// After an intended change, run the tests with `UPDATE_SNAPSHOTS=1` to rewrite the snapshots and review the diff.
+    macro_rules! assert_params_snapshot {
+        ($test:ident, $params:ty, $fixture:literal) => {
+            #[test]
+            fn $test() {
+                let params: $params = get_fixture($fixture);
+                let result = serde_json::to_value(&params).unwrap();
+                let path = concat!("fixtures/snapshots/", $fixture, ".json");
+
+                if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+                    write_snapshot(path, &result);
+                }
+
+                let snapshot: Value = get_fixture(concat!("snapshots/", $fixture));
+
+                assert_eq!(
+                    result,
+                    snapshot,
+                    "{} doesn't match its snapshot",
+                    stringify!($params)
+                );
+            }
+        };
+    }
+
+    fn write_snapshot(path: &str, value: &Value) {
+        let mut buffer = Vec::new();
+        let mut serializer =
+            serde_json::Serializer::with_formatter(&mut buffer, serde_json::ser::PrettyFormatter::with_indent(b"    "));
+
+        value.serialize(&mut serializer).unwrap();
+        buffer.push(b'\n');
+
+        std::fs::write(path, buffer).unwrap();
+    }
+
+    assert_params_snapshot!(
+        should_serialize_benefit_create_params,
+        BenefitCreateParams,
+        "benefit_create_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_checkout_session_params,
+        CheckoutSessionParams,
+        "checkout_session_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_custom_field_params,
+        CustomFieldParams,
+        "custom_field_params"
+    );
+    assert_params_snapshot!(should_serialize_customer_params, CustomerParams, "customer_params");
+    assert_params_snapshot!(should_serialize_discount_params, DiscountParams, "discount_params");
+    assert_params_snapshot!(
+        should_serialize_update_discount_params,
+        UpdateDiscountParams,
+        "update_discount_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_update_file_params,
+        UpdateFileParams,
+        "update_file_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_update_license_key_params,
+        UpdateLicenseKeyParams,
+        "update_license_key_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_file_create_params,
+        FileCreateParams,
+        "file_create_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_file_upload_completed_params,
+        FileUploadCompletedParams,
+        "file_upload_completed_params"
+    );
+    assert_params_snapshot!(should_serialize_meter_params, MeterParams, "meter_params");
+    assert_params_snapshot!(should_serialize_product_params, ProductParams, "product_params");
+    assert_params_snapshot!(
+        should_serialize_subscription_params,
+        SubscriptionParams,
+        "subscription_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_update_benefit_params,
+        UpdateBenefitParams,
+        "update_benefit_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_update_checkout_link_params,
+        UpdateCheckoutLinkParams,
+        "update_checkout_link_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_update_custom_field_params,
+        UpdateCustomFieldParams,
+        "update_custom_field_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_update_customer_params,
+        UpdateCustomerParams,
+        "update_customer_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_update_meter_params,
+        UpdateMeterParams,
+        "update_meter_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_update_order_params,
+        UpdateOrderParams,
+        "update_order_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_update_product_benefits_params,
+        UpdateProductBenefitsParams,
+        "update_product_benefits_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_update_product_params,
+        UpdateProductParams,
+        "update_product_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_update_webhook_endpoint_params,
+        UpdateWebhookEndpointParams,
+        "update_webhook_endpoint_params"
+    );
+    assert_params_snapshot!(
+        should_serialize_webhook_endpoint_params,
+        WebhookEndpointParams,
+        "webhook_endpoint_params"
+    );
+
+    fn get_product(prices: Value) -> Product {
+        let mut product: Value = get_fixture("product");
+
+        product["recurring_interval"] = json!("month");
+        product["prices"] = prices;
+
+        from_value(product).unwrap()
+    }
+
+    fn get_price(id: u128, amount_type: &str, currency: Option<&str>, is_archived: bool) -> Value {
+        json!({
+            "created_at": "2023-11-07T05:31:56Z",
+            "modified_at": null,
+            "id": Uuid::from_u128(id),
+            "amount_type": amount_type,
+            "is_archived": is_archived,
+            "product_id": "00a36059-1ca5-4bf2-8ceb-b9dc090ebb1c",
+            "type": "recurring",
+            "price_currency": currency,
+        })
+    }
+
+    #[test]
+    fn should_serialize_only_present_customer_fields() {
+        let params = UpdateCustomerParams {
+            email: Some("customer@example.com".to_owned()),
+            name: Some(None),
+            ..Default::default()
+        };
+
+        let result = serde_json::to_value(&params).unwrap();
+
+        assert_eq!(result, json!({ "email": "customer@example.com", "name": null }));
+    }
+
+    #[test]
+    fn should_serialize_only_present_checkout_link_fields() {
+        let result = serde_json::to_value(UpdateCheckoutLinkParams::default()).unwrap();
+
+        assert_eq!(result, json!({}));
+
+        let params = UpdateCheckoutLinkParams {
+            products: Some(vec![Uuid::nil()]),
+            discount_id: Some(None),
+            ..Default::default()
+        };
+
+        let result = serde_json::to_value(&params).unwrap();
+
+        assert_eq!(result, json!({ "products": [Uuid::nil()], "discount_id": null }));
+    }
+
+    #[test]
+    fn should_serialize_only_present_order_fields() {
+        let params = UpdateOrderParams {
+            billing_name: Some("John Doe".to_owned()),
+            ..Default::default()
+        };
+
+        let result = serde_json::to_value(&params).unwrap();
+
+        assert_eq!(result, json!({ "billing_name": "John Doe" }));
+    }
+
+    #[test]
+    fn should_serialize_attached_custom_fields_in_order() {
+        let field = |id, order| AttachedCustomFieldParams {
+            custom_field_id: Uuid::from_u128(id),
+            required: false,
+            order,
+        };
+        let params = UpdateProductParams {
+            attached_custom_fields: Some(vec![field(1, None), field(2, Some(1)), field(3, Some(0))]),
+            ..Default::default()
+        };
+
+        let result = serde_json::to_value(&params).unwrap();
+        let ids = result["attached_custom_fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|field| field["custom_field_id"].as_str().unwrap().to_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, [3, 2, 1].map(|id| Uuid::from_u128(id).to_string()));
+    }
+
+    #[test]
+    fn should_get_product_billing_type() {
+        let mut product = get_product(json!([get_price(1, "fixed", Some("usd"), false)]));
+
+        assert_eq!(
+            product.billing_type(),
+            ProductBillingType::Recurring(RecurringInterval::Month)
+        );
+
+        product.recurring_interval = None;
+        product.is_recurring = false;
+
+        assert_eq!(product.billing_type(), ProductBillingType::OneTime);
+
+        product.is_recurring = true;
+        product.prices[0].recurring_interval = Some(RecurringInterval::Year);
+
+        assert_eq!(
+            product.billing_type(),
+            ProductBillingType::Recurring(RecurringInterval::Year)
+        );
+    }
+
+    #[test]
+    fn should_filter_products_by_billing_type() {
+        let params = ListProductsParams::default().billing_type(ProductBillingType::OneTime);
+
+        assert_eq!(params.is_recurring, Some(false));
+    }
+
+    #[test]
+    fn should_select_price_in_currency() {
+        let product = get_product(json!([
+            get_price(1, "fixed", Some("usd"), true),
+            get_price(2, "metered_unit", Some("eur"), false),
+            get_price(3, "free", None, false),
+            get_price(4, "fixed", Some("eur"), false),
+        ]));
+
+        let price = product.price_for("EUR", Some(RecurringInterval::Month));
+
+        assert_eq!(price.map(|price| price.id), Some(Uuid::from_u128(4)));
+    }
+
+    #[test]
+    fn should_fall_back_to_free_price() {
+        let product = get_product(json!([
+            get_price(1, "fixed", Some("usd"), true),
+            get_price(2, "free", None, false),
+        ]));
+
+        let price = product.price_for("usd", Some(RecurringInterval::Month));
+
+        assert_eq!(price.map(|price| price.id), Some(Uuid::from_u128(2)));
+    }
+
+    #[test]
+    fn should_not_select_price_with_other_interval() {
+        let product = get_product(json!([get_price(1, "fixed", Some("usd"), false)]));
+
+        assert!(product.price_for("usd", Some(RecurringInterval::Year)).is_none());
+        assert!(product.price_for("usd", None).is_none());
+    }
+
+    #[test]
+    fn should_deserialize_amounts_above_32_bits() {
+        let mut order: Value = get_fixture("order");
+
+        order["net_amount"] = json!(5_000_000_000_i64);
+        order["total_amount"] = json!(5_000_000_000_i64);
+
+        let order: Order = from_value(order).unwrap();
+
+        assert_eq!(order.net_amount, 5_000_000_000);
+        assert_eq!(order.total_amount, 5_000_000_000);
+    }
+
+    #[test]
+    fn should_prefill_checkout_from_customer() {
+        let mut customer: Value = get_fixture("customer");
+
+        customer["tax_id"] = json!(["FR61954506077", "eu_vat"]);
+
+        let customer: Customer = from_value(customer).unwrap();
+
+        let params = CheckoutSessionParams {
+            products: vec![Uuid::new_v4()],
+            ..Default::default()
+        }
+        .prefill_from(&customer);
+
+        assert_eq!(params.customer_id, Some(customer.id));
+        assert_eq!(params.external_customer_id, customer.external_id);
+        assert_eq!(params.customer_email.as_ref(), Some(&customer.email));
+        assert_eq!(params.customer_billing_name, customer.name);
+        assert_eq!(params.customer_tax_id.as_deref(), Some("FR61954506077"));
+        assert_eq!(
+            params.customer_billing_address.map(|address| address.country),
+            customer.billing_address.map(|address| address.country)
+        );
+    }
+
+    #[test]
+    fn should_normalize_billing_address() {
+        let mut address = CustomerBillingAddress {
+            country: " us ".to_owned(),
+            line1: Some(" 1 Main St ".to_owned()),
+            line2: Some("  ".to_owned()),
+            postal_code: Some("94107-1234 ".to_owned()),
+            city: Some("San Francisco".to_owned()),
+            state: Some(" US-CA ".to_owned()),
+        };
+
+        address.normalize().unwrap();
+
+        assert_eq!(address.country, "US");
+        assert_eq!(address.line1.as_deref(), Some("1 Main St"));
+        assert_eq!(address.line2, None);
+        assert_eq!(address.postal_code.as_deref(), Some("94107-1234"));
+        assert_eq!(address.state.as_deref(), Some("US-CA"));
+
+        let mut params = CustomerParams {
+            billing_address: Some(CustomerBillingAddress {
+                country: "gb".to_owned(),
+                line1: None,
+                line2: None,
+                postal_code: Some("sw1a 1aa".to_owned()),
+                city: None,
+                state: None,
+            }),
+            ..Default::default()
+        };
+
+        params.normalize_billing_address().unwrap();
+
+        assert_eq!(
+            params
+                .billing_address
+                .and_then(|address| address.postal_code)
+                .as_deref(),
+            Some("SW1A 1AA")
+        );
+    }
+
+    #[test]
+    fn should_not_normalize_invalid_billing_address() {
+        let address = |country: &str, postal_code: &str| CustomerBillingAddress {
+            country: country.to_owned(),
+            line1: None,
+            line2: None,
+            postal_code: Some(postal_code.to_owned()),
+            city: None,
+            state: None,
+        };
+
+        assert!(address("USA", "94107").normalize().is_err());
+        assert!(address("US", "9410").normalize().is_err());
+        assert!(address("CA", "12345").normalize().is_err());
+        assert!(address("FR", "75 001").normalize().is_err());
+        assert!(address("ZZ", "anything").normalize().is_ok());
+    }
+
+    #[test]
+    fn should_serialize_customer_ip_address() {
+        let params = CheckoutSessionParams {
+            customer_ip_address: Some("2001:db8::1".parse().unwrap()),
+            ..Default::default()
+        };
+
+        let result = serde_json::to_value(&params).unwrap();
+
+        assert_eq!(result["customer_ip_address"], json!("2001:db8::1"));
+    }
+
+    #[test]
+    fn should_not_deserialize_invalid_customer_ip_address() {
+        let mut params: Value = get_fixture("checkout_session_params");
+
+        assert!(from_value::<CheckoutSessionParams>(params.clone()).is_ok());
+
+        params["customer_ip_address"] = json!("<string>");
+
+        let result = from_value::<CheckoutSessionParams>(params);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_diff_webhook_endpoint_events() {
+        let endpoint: WebhookEndpoint = get_fixture("webhook_endpoint");
+
+        assert!(
+            endpoint
+                .events_diff(&[WebhookEventType::OrderPaid, WebhookEventType::OrderCreated])
+                .is_empty()
+        );
+        assert_eq!(
+            endpoint.events_diff(&[WebhookEventType::OrderPaid, WebhookEventType::OrderRefunded]),
+            WebhookEventsDiff {
+                added: vec![WebhookEventType::OrderRefunded],
+                removed: vec![WebhookEventType::OrderCreated],
+            }
+        );
+    }
+
+    #[test]
+    fn should_decode_benefit_properties_by_type() {
+        let benefit: Benefit = get_fixture("benefit");
+
+        let result = benefit.typed_properties();
+
+        assert!(matches!(
+            result,
+            Ok(Some(BenefitProperties::LicenseKeys(BenefitLicenseKeysProperties {
+                limit_usage: Some(123),
+                ..
+            })))
+        ));
+    }
+
+    #[test]
+    fn should_not_decode_missing_benefit_properties() {
+        let checkout_session: CheckoutSession = get_fixture("checkout_session");
+
+        let result = checkout_session.products[0].benefits[0].typed_properties();
+
+        assert!(result.is_ok_and(|properties| properties.is_none()));
+    }
+}
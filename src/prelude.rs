@@ -0,0 +1,10 @@
+//! Commonly used types, meant to be glob imported.
+//!
+//! ```
+//! use polar_rs::prelude::*;
+//! ```
+
+pub use crate::{
+    CheckoutSession, CheckoutSessionParams, Customer, ListResponse, Page, Pagination, Polar, PolarError, PolarResult,
+    Product, Subscription, Subsystem, shutdown_all,
+};
@@ -0,0 +1,271 @@
+//! Opinionated helpers covering a whole billing loop: a product to sell, a checkout to buy it, the webhooks telling
+//! what happened and an access check.
+//!
+//! They are built on the rest of the crate, so they double as examples of it. Use the underlying methods of [`Polar`]
+//! when you need more control.
+//!
+//! ```no_run
+//! use polar_rs::Polar;
+//! use polar_rs::quickstart;
+//!
+//! # async fn run() -> polar_rs::PolarResult<()> {
+//! let polar = Polar::new("https://sandbox-api.polar.sh/v1/", "<YOUR ACCESS TOKEN>")?;
+//!
+//! let product = quickstart::ensure_product(&polar, "Pro plan", 1000, "usd", None).await?;
+//! let checkout = quickstart::create_checkout_for(&polar, product.id, "usr_1337", "https://example.com/success").await?;
+//!
+//! println!("Send the customer to {}", checkout.url);
+//! # Ok(())
+//! # }
+//! ```
+
+use reqwest::IntoUrl;
+use reqwest::header::HeaderMap;
+use uuid::Uuid;
+
+use crate::{
+    AmountType, CheckoutSession, CheckoutSessionParams, ListOrdersParams, ListProductsParams, ListSubscriptionsParams,
+    Polar, PolarError, PolarResult, PriceParams, Product, ProductParams, RecurringInterval, WebhookEvent,
+    WebhookHeaders, build_success_url, verify_webhook,
+};
+
+/// Returns the product with the given name and recurring interval, creating it with a fixed price if it doesn't exist.
+///
+/// Archived products are ignored. The price of an existing product is left as is.
+pub async fn ensure_product(
+    polar: &Polar,
+    name: &str,
+    amount: i64,
+    currency: &str,
+    recurring_interval: Option<RecurringInterval>,
+) -> PolarResult<Product> {
+    let params = ListProductsParams {
+        query: Some(name.to_owned()),
+        is_archived: Some(false),
+        limit: Some(100),
+        ..Default::default()
+    };
+    let product = polar
+        .list_products(&params)
+        .await?
+        .find_item(polar, |product| {
+            product.name == name && product.recurring_interval == recurring_interval
+        })
+        .await?;
+
+    if let Some(product) = product {
+        return Ok(product);
+    }
+
+    polar
+        .create_product(&ProductParams {
+            name: name.to_owned(),
+            recurring_interval,
+            prices: vec![PriceParams {
+                amount_type: AmountType::Fixed,
+                price_currency: Some(currency.to_owned()),
+                price_amount: Some(amount),
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .await
+}
+
+/// Creates a checkout session to buy a product, linked to the ID of the customer in your system.
+///
+/// The customer is redirected to `success_url` after paying, with a `checkout_id` query param.
+pub async fn create_checkout_for<U: IntoUrl>(
+    polar: &Polar,
+    product_id: Uuid,
+    external_customer_id: &str,
+    success_url: U,
+) -> PolarResult<CheckoutSession> {
+    let success_url = build_success_url(success_url, std::iter::empty::<(&str, &str)>())?;
+
+    polar
+        .create_checkout_session(&CheckoutSessionParams {
+            products: vec![product_id],
+            external_customer_id: Some(external_customer_id.to_owned()),
+            success_url: Some(success_url),
+            ..Default::default()
+        })
+        .await
+}
+
+/// Verifies and decodes a webhook request, given its headers and raw body.
+///
+/// Fails with [`PolarError::Unauthorized`] if the request wasn't sent by Polar, answer with `403 Forbidden` then.
+pub fn handle_webhook(secret: &str, headers: &HeaderMap, body: &[u8]) -> PolarResult<WebhookEvent> {
+    let headers = WebhookHeaders::from_header_map(headers).ok_or(PolarError::Unauthorized)?;
+
+    verify_webhook(secret, &headers, body)
+}
+
+/// Whether a customer has access to a product, either with an active subscription or a paid one-time purchase that
+/// wasn't fully refunded.
+pub async fn customer_has_access(polar: &Polar, customer_id: Uuid, product_id: Uuid) -> PolarResult<bool> {
+    let subscriptions = polar
+        .list_subscriptions(&ListSubscriptionsParams {
+            customer_id: Some(vec![customer_id]),
+            product_id: Some(vec![product_id]),
+            active: Some(true),
+            limit: Some(1),
+            ..Default::default()
+        })
+        .await?;
+
+    if subscriptions.pagination.total_count > 0 {
+        return Ok(true);
+    }
+
+    let params = ListOrdersParams {
+        customer_id: Some(vec![customer_id]),
+        product_id: Some(vec![product_id]),
+        limit: Some(100),
+        ..Default::default()
+    };
+    // Orders of subscriptions don't grant access once the subscription is over.
+    let order = polar
+        .list_orders(&params)
+        .await?
+        .find_item(polar, |order| {
+            order.subscription_id.is_none() && order.paid && !order.status.is_refunded()
+        })
+        .await?;
+
+    Ok(order.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    use super::*;
+    use crate::test_support::get_fixture;
+
+    async fn mount_page(mock: &MockServer, path: &str, items: Vec<Value>) {
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path(path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "items": items,
+                "pagination": { "total_count": items.len(), "max_page": 1 },
+            })))
+            .mount(mock)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn should_ensure_existing_product() {
+        let mock = MockServer::start().await;
+        let mut product = get_fixture::<Value>("product");
+
+        product["name"] = json!("Pro plan");
+        product["recurring_interval"] = json!(null);
+
+        mount_page(&mock, "/products", vec![product]).await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let result = ensure_product(&polar, "Pro plan", 1000, "usd", None).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_ensure_product_by_creating_it() {
+        let mock = MockServer::start().await;
+
+        mount_page(&mock, "/products", vec![]).await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/products"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(get_fixture::<Value>("product")))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let result = ensure_product(&polar, "Pro plan", 1000, "usd", Some(RecurringInterval::Month)).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_create_checkout_for_customer() {
+        let mock = MockServer::start().await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/checkouts"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(get_fixture::<Value>("checkout_session")))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let result = create_checkout_for(&polar, Uuid::new_v4(), "usr_1337", "https://example.com/success").await;
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn should_not_handle_webhook_without_headers() {
+        let result = handle_webhook("polar_whs_secret", &HeaderMap::new(), b"{}");
+
+        assert!(matches!(result, Err(PolarError::Unauthorized)));
+    }
+
+    #[tokio::test]
+    async fn should_grant_access_with_active_subscription() {
+        let mock = MockServer::start().await;
+
+        mount_page(&mock, "/subscriptions", vec![get_fixture::<Value>("subscription")]).await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let result = customer_has_access(&polar, Uuid::new_v4(), Uuid::new_v4()).await;
+
+        assert!(result.is_ok_and(|has_access| has_access));
+    }
+
+    #[tokio::test]
+    async fn should_grant_access_with_paid_order() {
+        let mock = MockServer::start().await;
+        let mut order = get_fixture::<Value>("order");
+
+        order["subscription_id"] = json!(null);
+        order["paid"] = json!(true);
+        order["status"] = json!("paid");
+
+        mount_page(&mock, "/subscriptions", vec![]).await;
+        mount_page(&mock, "/orders", vec![order]).await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let result = customer_has_access(&polar, Uuid::new_v4(), Uuid::new_v4()).await;
+
+        assert!(result.is_ok_and(|has_access| has_access));
+    }
+
+    #[tokio::test]
+    async fn should_not_grant_access_with_refunded_order() {
+        let mock = MockServer::start().await;
+        let mut order = get_fixture::<Value>("order");
+
+        order["subscription_id"] = json!(null);
+        order["paid"] = json!(true);
+        order["status"] = json!("refunded");
+
+        mount_page(&mock, "/subscriptions", vec![]).await;
+        mount_page(&mock, "/orders", vec![order]).await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let result = customer_has_access(&polar, Uuid::new_v4(), Uuid::new_v4()).await;
+
+        assert!(result.is_ok_and(|has_access| !has_access));
+    }
+}
@@ -0,0 +1,236 @@
+//! Multi-step operations undone on failure, e.g. creating a benefit and a discount that only make sense together.
+//!
+//! Each step is a call to [`Polar`]. The resources created by the steps are recorded, and when a step fails they're
+//! deleted in reverse order, on a best-effort basis.
+//!
+//! ```no_run
+//! use polar_rs::saga::{Resource, Saga};
+//! use polar_rs::{BenefitCreateParams, DiscountParams, Polar};
+//!
+//! # async fn run(
+//! #     polar: &Polar,
+//! #     benefit: BenefitCreateParams,
+//! #     discount: DiscountParams,
+//! # ) -> Result<(), polar_rs::saga::SagaFailure> {
+//! let mut saga = Saga::new(polar);
+//!
+//! let benefit = saga
+//!     .create("create benefit", polar.create_benefit(&benefit), |benefit| Resource::Benefit(benefit.id))
+//!     .await?;
+//! saga
+//!     .create("create discount", polar.create_discount(&discount), |discount| Resource::Discount(discount.id))
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::error::Error;
+use std::fmt::Display;
+
+use uuid::Uuid;
+
+use crate::{Polar, PolarError, PolarResult};
+
+/// A resource created by a step, deleted if a later step fails.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Resource {
+    Benefit(Uuid),
+    CheckoutLink(Uuid),
+    CustomField(Uuid),
+    Customer(Uuid),
+    Discount(Uuid),
+    File(Uuid),
+}
+
+impl Resource {
+    async fn delete(self, polar: &Polar) -> PolarResult<()> {
+        match self {
+            Resource::Benefit(id) => polar.delete_benefit(id).await,
+            Resource::CheckoutLink(id) => polar.delete_checkout_link(id).await,
+            Resource::CustomField(id) => polar.delete_custom_field(id).await,
+            Resource::Customer(id) => polar.delete_customer(id).await,
+            Resource::Discount(id) => polar.delete_discount(id).await,
+            Resource::File(id) => polar.delete_file(id).await,
+        }
+    }
+}
+
+/// Tracks the completed steps of an operation, and the resources they created.
+pub struct Saga<'a> {
+    polar: &'a Polar,
+    completed_steps: Vec<&'static str>,
+    created: Vec<Resource>,
+}
+
+/// Why an operation failed, and what was undone.
+#[derive(Debug)]
+pub struct SagaFailure {
+    /// Name of the step that failed.
+    pub step: &'static str,
+    /// Error of the step that failed.
+    pub error: PolarError,
+    /// Names of the steps completed before the failure, in order.
+    pub completed_steps: Vec<&'static str>,
+    /// Resources deleted after the failure.
+    pub compensated: Vec<Resource>,
+    /// Resources that couldn't be deleted after the failure, to be cleaned up manually.
+    pub not_compensated: Vec<(Resource, PolarError)>,
+}
+
+impl Display for SagaFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Step {:?} failed: {}", self.step, self.error)?;
+
+        if !self.not_compensated.is_empty() {
+            write!(f, " ({} resources left behind)", self.not_compensated.len())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for SagaFailure {}
+
+impl<'a> Saga<'a> {
+    pub fn new(polar: &'a Polar) -> Self {
+        Self {
+            polar,
+            completed_steps: Vec::new(),
+            created: Vec::new(),
+        }
+    }
+
+    /// Names of the steps completed so far, in order.
+    pub fn completed_steps(&self) -> &[&'static str] {
+        &self.completed_steps
+    }
+
+    /// Runs a step not creating anything, e.g. an update. If it fails, the resources created by the previous steps
+    /// are deleted.
+    pub async fn step<T, F>(&mut self, name: &'static str, call: F) -> Result<T, SagaFailure>
+    where
+        F: Future<Output = PolarResult<T>>,
+    {
+        match call.await {
+            Ok(value) => {
+                self.completed_steps.push(name);
+
+                Ok(value)
+            }
+            Err(error) => Err(self.compensate(name, error).await),
+        }
+    }
+
+    /// Runs a step creating a resource, identified by `resource` from the result of the call. The resource is
+    /// deleted if a later step fails.
+    pub async fn create<T, F, R>(&mut self, name: &'static str, call: F, resource: R) -> Result<T, SagaFailure>
+    where
+        F: Future<Output = PolarResult<T>>,
+        R: FnOnce(&T) -> Resource,
+    {
+        let value = self.step(name, call).await?;
+
+        self.created.push(resource(&value));
+
+        Ok(value)
+    }
+
+    async fn compensate(&mut self, step: &'static str, error: PolarError) -> SagaFailure {
+        let mut compensated = Vec::new();
+        let mut not_compensated = Vec::new();
+
+        while let Some(resource) = self.created.pop() {
+            match resource.delete(self.polar).await {
+                Ok(()) | Err(PolarError::NotFound) => compensated.push(resource),
+                Err(error) => not_compensated.push((resource, error)),
+            }
+        }
+
+        SagaFailure {
+            step,
+            error,
+            completed_steps: std::mem::take(&mut self.completed_steps),
+            compensated,
+            not_compensated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    use super::*;
+    use crate::test_support::get_fixture;
+    use crate::{BenefitCreateParams, DiscountParams};
+
+    async fn mount(mock: &MockServer, method: &str, path: &str, status: u16, fixture: &str) {
+        Mock::given(matchers::method(method))
+            .and(matchers::path(path))
+            .respond_with(ResponseTemplate::new(status).set_body_json(get_fixture::<Value>(fixture)))
+            .expect(1)
+            .mount(mock)
+            .await;
+    }
+
+    async fn run(polar: &Polar) -> Result<(), SagaFailure> {
+        let benefit_params: BenefitCreateParams = get_fixture("benefit_create_params");
+        let discount_params: DiscountParams = get_fixture("discount_params");
+        let mut saga = Saga::new(polar);
+
+        saga.create("create benefit", polar.create_benefit(&benefit_params), |benefit| {
+            Resource::Benefit(benefit.id)
+        })
+        .await?;
+        saga.create("create discount", polar.create_discount(&discount_params), |discount| {
+            Resource::Discount(discount.id)
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_delete_created_resources_on_failure() {
+        let benefit_id = get_fixture::<Value>("benefit")["id"].as_str().unwrap().to_owned();
+        let mock = MockServer::start().await;
+
+        mount(&mock, "POST", "/benefits", 201, "benefit").await;
+        mount(&mock, "POST", "/discounts", 422, "unprocessable_entity").await;
+        mount(&mock, "DELETE", &format!("/benefits/{benefit_id}"), 204, "benefit").await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let failure = run(&polar).await.unwrap_err();
+
+        assert_eq!(failure.step, "create discount");
+        assert_eq!(failure.completed_steps, ["create benefit"]);
+        assert_eq!(failure.compensated, [Resource::Benefit(benefit_id.parse().unwrap())]);
+        assert!(failure.not_compensated.is_empty());
+    }
+
+    #[tokio::test]
+    async fn should_report_resources_not_deleted() {
+        let benefit_id = get_fixture::<Value>("benefit")["id"].as_str().unwrap().to_owned();
+        let mock = MockServer::start().await;
+
+        mount(&mock, "POST", "/benefits", 201, "benefit").await;
+        mount(&mock, "POST", "/discounts", 422, "unprocessable_entity").await;
+        mount(
+            &mock,
+            "DELETE",
+            &format!("/benefits/{benefit_id}"),
+            500,
+            "unprocessable_entity",
+        )
+        .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let failure = run(&polar).await.unwrap_err();
+
+        assert!(failure.compensated.is_empty());
+        assert_eq!(failure.not_compensated.len(), 1);
+    }
+}
@@ -0,0 +1,106 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::{PolarError, PolarResult};
+
+/// Future returned by [`Subsystem::shutdown`].
+pub type ShutdownFuture<'a> = Pin<Box<dyn Future<Output = PolarResult<()>> + Send + 'a>>;
+
+/// A background component, e.g. an event batcher or a cache, owning tasks that must be stopped before the
+/// application exits.
+///
+/// Applications should drive every subsystem they use from their own shutdown hook, either one by one or through
+/// [`shutdown_all`]. It's implemented by [`EventSink`](crate::EventSink), and should be by the
+/// [`CustomerStateCache`](crate::CustomerStateCache) implementations running background tasks, e.g. to expire entries.
+pub trait Subsystem: Send {
+    /// Name used to identify the subsystem when reporting shutdown errors.
+    fn name(&self) -> &str;
+
+    /// Flushes any pending work and waits for the background tasks to finish.
+    ///
+    /// Calling it on a subsystem already shut down must succeed without doing anything.
+    fn shutdown(&mut self) -> ShutdownFuture<'_>;
+}
+
+/// Shuts down the given subsystems in order.
+///
+/// A failing subsystem doesn't prevent the next ones from being shut down, the errors are returned along with the name
+/// of their subsystem.
+pub async fn shutdown_all(subsystems: &mut [&mut dyn Subsystem]) -> Result<(), Vec<(String, PolarError)>> {
+    let mut errors = Vec::new();
+
+    for subsystem in subsystems {
+        if let Err(err) = subsystem.shutdown().await {
+            errors.push((subsystem.name().to_owned(), err));
+        }
+    }
+
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use super::*;
+
+    struct TestSubsystem {
+        name: &'static str,
+        fails: bool,
+        is_shut_down: AtomicBool,
+    }
+
+    impl TestSubsystem {
+        fn new(name: &'static str, fails: bool) -> Self {
+            Self {
+                name,
+                fails,
+                is_shut_down: AtomicBool::new(false),
+            }
+        }
+    }
+
+    impl Subsystem for TestSubsystem {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn shutdown(&mut self) -> ShutdownFuture<'_> {
+            Box::pin(async move {
+                self.is_shut_down.store(true, Ordering::SeqCst);
+
+                if self.fails {
+                    Err(PolarError::Unknown("failed".to_owned()))
+                } else {
+                    Ok(())
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn should_shut_down_all_subsystems() {
+        let mut first = TestSubsystem::new("first", false);
+        let mut second = TestSubsystem::new("second", false);
+
+        let result = shutdown_all(&mut [&mut first, &mut second]).await;
+
+        assert!(result.is_ok());
+        assert!(first.is_shut_down.load(Ordering::SeqCst));
+        assert!(second.is_shut_down.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn should_shut_down_remaining_subsystems_after_error() {
+        let mut first = TestSubsystem::new("first", true);
+        let mut second = TestSubsystem::new("second", false);
+
+        let result = shutdown_all(&mut [&mut first, &mut second]).await;
+
+        let errors = result.unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "first");
+        assert!(second.is_shut_down.load(Ordering::SeqCst));
+    }
+}
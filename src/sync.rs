@@ -0,0 +1,699 @@
+//! Declarative sync of the product catalog: the products are declared in code, or loaded from a TOML file with the
+//! `toml` feature, then diffed against the live catalog to get a plan of the changes, which can be reviewed before being
+//! applied.
+//!
+//! Products are matched by name. Applying a plan twice is harmless, the second plan being empty.
+//!
+//! ```no_run
+//! use polar_rs::Polar;
+//! use polar_rs::sync::{self, Catalog, DesiredPrice, DesiredProduct};
+//!
+//! # async fn run(polar: &Polar) -> polar_rs::PolarResult<()> {
+//! let catalog = Catalog {
+//!     products: vec![DesiredProduct {
+//!         name: "Pro plan".to_owned(),
+//!         description: None,
+//!         recurring_interval: Some(polar_rs::RecurringInterval::Month),
+//!         price: DesiredPrice::Fixed {
+//!             amount: 1000,
+//!             currency: "usd".to_owned(),
+//!         },
+//!         benefits: Vec::new(),
+//!         metadata: Default::default(),
+//!     }],
+//!     ..Default::default()
+//! };
+//!
+//! let plan = sync::plan(polar, &catalog).await?;
+//!
+//! println!("{plan}");
+//!
+//! sync::apply(polar, &plan).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Display;
+
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    AmountType, ListProductsParams, Polar, PolarResult, Price, PriceParams, Product, ProductParams, RecurringInterval,
+    UpdatePriceParams, UpdateProductBenefitsParams, UpdateProductParams,
+};
+
+/// The products the catalog should have.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Catalog {
+    /// The ID of the organization owning the products. **Required unless you use an organization token.**
+    #[serde(default)]
+    pub organization_id: Option<Uuid>,
+    /// Whether live products not declared in the catalog are archived.
+    #[serde(default)]
+    pub archive_unlisted: bool,
+    pub products: Vec<DesiredProduct>,
+}
+
+impl Catalog {
+    /// Parses a catalog from TOML, each product being a `[[products]]` table and its price a `price` table tagged by
+    /// `amount_type`. Requires the `toml` feature.
+    ///
+    /// ```
+    /// let catalog = polar_rs::sync::Catalog::from_toml_str(
+    ///     r#"
+    ///     [[products]]
+    ///     name = "Pro plan"
+    ///     recurring_interval = "month"
+    ///     price = { amount_type = "fixed", amount = 1000, currency = "usd" }
+    ///     "#,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(catalog.products[0].name, "Pro plan");
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+}
+
+/// A product as it should be, identified by its name.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct DesiredProduct {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The recurring interval of the product. If `None`, the product is a one-time purchase.
+    #[serde(default)]
+    pub recurring_interval: Option<RecurringInterval>,
+    pub price: DesiredPrice,
+    /// IDs of the benefits granted by the product.
+    #[serde(default)]
+    pub benefits: Vec<Uuid>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+/// The static price of a product. Amounts are in cents.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(tag = "amount_type", rename_all = "snake_case")]
+pub enum DesiredPrice {
+    Fixed {
+        amount: i64,
+        currency: String,
+    },
+    Custom {
+        currency: String,
+        #[serde(default)]
+        minimum_amount: Option<i64>,
+        #[serde(default)]
+        maximum_amount: Option<i64>,
+        #[serde(default)]
+        preset_amount: Option<i64>,
+    },
+    Free,
+}
+
+impl DesiredPrice {
+    fn matches(&self, price: &Price) -> bool {
+        let same_currency = |currency: &str| {
+            price
+                .price_currency
+                .as_deref()
+                .is_some_and(|price_currency| price_currency.eq_ignore_ascii_case(currency))
+        };
+
+        match self {
+            DesiredPrice::Fixed { amount, currency } => {
+                matches!(price.amount_type, AmountType::Fixed)
+                    && price.price_amount == Some(*amount)
+                    && same_currency(currency)
+            }
+            DesiredPrice::Custom {
+                currency,
+                minimum_amount,
+                maximum_amount,
+                preset_amount,
+            } => {
+                matches!(price.amount_type, AmountType::Custom)
+                    && same_currency(currency)
+                    && price.minimum_amount == *minimum_amount
+                    && price.maximum_amount == *maximum_amount
+                    && price.preset_amount == *preset_amount
+            }
+            DesiredPrice::Free => matches!(price.amount_type, AmountType::Free),
+        }
+    }
+
+    fn to_params(&self) -> PriceParams {
+        match self {
+            DesiredPrice::Fixed { amount, currency } => PriceParams {
+                amount_type: AmountType::Fixed,
+                price_currency: Some(currency.clone()),
+                price_amount: Some(*amount),
+                ..Default::default()
+            },
+            DesiredPrice::Custom {
+                currency,
+                minimum_amount,
+                maximum_amount,
+                preset_amount,
+            } => PriceParams {
+                amount_type: AmountType::Custom,
+                price_currency: Some(currency.clone()),
+                minimum_amount: *minimum_amount,
+                maximum_amount: *maximum_amount,
+                preset_amount: *preset_amount,
+                ..Default::default()
+            },
+            DesiredPrice::Free => PriceParams {
+                amount_type: AmountType::Free,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// A change to apply to the live catalog.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Change {
+    /// A declared product missing from the catalog.
+    Create(DesiredProduct),
+    /// A declared product differing from the live one, `fields` naming what differs.
+    Update {
+        id: Uuid,
+        product: DesiredProduct,
+        fields: Vec<&'static str>,
+    },
+    /// A live product not declared in the catalog, only with [`Catalog::archive_unlisted`].
+    Archive { id: Uuid, name: String },
+}
+
+/// The changes needed for the live catalog to match the declared one.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Plan {
+    pub organization_id: Option<Uuid>,
+    pub changes: Vec<Change>,
+}
+
+impl Plan {
+    /// Whether the live catalog already matches the declared one.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl Display for Plan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "No changes");
+        }
+
+        for change in &self.changes {
+            match change {
+                Change::Create(product) => writeln!(f, "+ create {:?}", product.name)?,
+                Change::Update { product, fields, .. } => {
+                    writeln!(f, "~ update {:?} ({})", product.name, fields.join(", "))?
+                }
+                Change::Archive { name, .. } => writeln!(f, "- archive {name:?}")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Lists the live products, not archived, and diffs them against the catalog. Nothing is changed.
+pub async fn plan(polar: &Polar, catalog: &Catalog) -> PolarResult<Plan> {
+    let params = ListProductsParams {
+        organization_id: catalog.organization_id.map(|id| vec![id]),
+        is_archived: Some(false),
+        limit: Some(100),
+        ..Default::default()
+    };
+    let live = polar.list_products(&params).await?.collect_items(polar).await?;
+
+    Ok(diff(catalog, &live))
+}
+
+/// Applies the changes of a plan, in order. When a change fails, the following ones are not applied, and planning again
+/// gives what's left to do.
+pub async fn apply(polar: &Polar, plan: &Plan) -> PolarResult<()> {
+    for change in &plan.changes {
+        match change {
+            Change::Create(desired) => {
+                let product = polar
+                    .create_product(&ProductParams {
+                        name: desired.name.clone(),
+                        recurring_interval: desired.recurring_interval,
+                        prices: vec![desired.price.to_params()],
+                        metadata: desired.metadata.clone(),
+                        description: desired.description.clone(),
+                        organization_id: plan.organization_id,
+                        ..Default::default()
+                    })
+                    .await?;
+
+                if !desired.benefits.is_empty() {
+                    update_benefits(polar, product.id, desired).await?;
+                }
+            }
+            Change::Update { id, product, fields } => {
+                if fields.iter().any(|field| *field != "benefits") {
+                    let live = polar.get_product(*id).await?;
+                    let prices = if fields.contains(&"price") {
+                        let replaced = static_price(&live).map(|price| price.id);
+                        let mut prices: Vec<_> = kept_prices(&live)
+                            .into_iter()
+                            .filter(|price| price.id != replaced)
+                            .collect();
+
+                        prices.push(new_price(product.price.to_params()));
+                        prices
+                    } else {
+                        kept_prices(&live)
+                    };
+
+                    polar
+                        .update_product(
+                            *id,
+                            &UpdateProductParams {
+                                metadata: product.metadata.clone(),
+                                description: product.description.clone(),
+                                recurring_interval: product.recurring_interval,
+                                prices,
+                                ..Default::default()
+                            },
+                        )
+                        .await?;
+                }
+
+                if fields.contains(&"benefits") {
+                    update_benefits(polar, *id, product).await?;
+                }
+            }
+            Change::Archive { id, .. } => {
+                let live = polar.get_product(*id).await?;
+
+                polar
+                    .update_product(
+                        *id,
+                        &UpdateProductParams {
+                            metadata: live.metadata.clone(),
+                            description: live.description.clone(),
+                            recurring_interval: live.recurring_interval,
+                            is_archived: Some(true),
+                            prices: kept_prices(&live),
+                            ..Default::default()
+                        },
+                    )
+                    .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn diff(catalog: &Catalog, live: &[Product]) -> Plan {
+    let mut changes = Vec::new();
+
+    for desired in &catalog.products {
+        let Some(product) = live.iter().find(|product| product.name == desired.name) else {
+            changes.push(Change::Create(desired.clone()));
+            continue;
+        };
+        let mut fields = Vec::new();
+
+        if product.description != desired.description {
+            fields.push("description");
+        }
+
+        if product.recurring_interval != desired.recurring_interval {
+            fields.push("recurring_interval");
+        }
+
+        if !static_price(product).is_some_and(|price| desired.price.matches(price)) {
+            fields.push("price");
+        }
+
+        if product.metadata != desired.metadata {
+            fields.push("metadata");
+        }
+
+        let live_benefits: BTreeSet<_> = product.benefits.iter().map(|benefit| benefit.id).collect();
+
+        if live_benefits != desired.benefits.iter().copied().collect() {
+            fields.push("benefits");
+        }
+
+        if !fields.is_empty() {
+            changes.push(Change::Update {
+                id: product.id,
+                product: desired.clone(),
+                fields,
+            });
+        }
+    }
+
+    if catalog.archive_unlisted {
+        changes.extend(
+            live.iter()
+                .filter(|product| !catalog.products.iter().any(|desired| desired.name == product.name))
+                .map(|product| Change::Archive {
+                    id: product.id,
+                    name: product.name.clone(),
+                }),
+        );
+    }
+
+    Plan {
+        organization_id: catalog.organization_id,
+        changes,
+    }
+}
+
+/// The fixed, custom or free price of a product, ignoring metered prices.
+fn static_price(product: &Product) -> Option<&Price> {
+    product
+        .prices
+        .iter()
+        .find(|price| !price.is_archived && !matches!(price.amount_type, AmountType::MeteredUnit))
+}
+
+fn kept_prices(product: &Product) -> Vec<UpdatePriceParams> {
+    product
+        .prices
+        .iter()
+        .filter(|price| !price.is_archived)
+        .map(|price| UpdatePriceParams {
+            id: Some(price.id),
+            amount_type: None,
+            price_currency: None,
+            price_amount: None,
+            minimum_amount: None,
+            maximum_amount: None,
+            preset_amount: None,
+            meter_id: None,
+            unit_amount: None,
+            cap_amount: None,
+        })
+        .collect()
+}
+
+fn new_price(params: PriceParams) -> UpdatePriceParams {
+    UpdatePriceParams {
+        id: None,
+        amount_type: Some(params.amount_type),
+        price_currency: params.price_currency,
+        price_amount: params.price_amount,
+        minimum_amount: params.minimum_amount,
+        maximum_amount: params.maximum_amount,
+        preset_amount: params.preset_amount,
+        meter_id: params.meter_id,
+        unit_amount: params.unit_amount,
+        cap_amount: params.cap_amount,
+    }
+}
+
+async fn update_benefits(polar: &Polar, id: Uuid, desired: &DesiredProduct) -> PolarResult<Product> {
+    polar
+        .update_product_benefits(
+            id,
+            &UpdateProductBenefitsParams {
+                benefits: desired.benefits.clone(),
+            },
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    use super::*;
+    use crate::test_support::get_fixture;
+
+    fn get_product() -> Value {
+        let mut product: Value = get_fixture("product");
+
+        product["name"] = json!("Pro plan");
+        product["description"] = json!(null);
+        product["is_archived"] = json!(false);
+        product["prices"][0]["is_archived"] = json!(false);
+        product["prices"][0]["price_currency"] = json!("usd");
+        product["prices"][0]["price_amount"] = json!(1000);
+        product["benefits"] = json!([]);
+
+        product
+    }
+
+    fn get_desired() -> DesiredProduct {
+        DesiredProduct {
+            name: "Pro plan".to_owned(),
+            description: None,
+            recurring_interval: Some(RecurringInterval::Month),
+            price: DesiredPrice::Fixed {
+                amount: 1000,
+                currency: "USD".to_owned(),
+            },
+            benefits: Vec::new(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn should_plan_nothing_when_catalog_matches() {
+        let live: Product = serde_json::from_value(get_product()).unwrap();
+        let catalog = Catalog {
+            products: vec![get_desired()],
+            ..Default::default()
+        };
+
+        let plan = diff(&catalog, &[live]);
+
+        assert!(plan.is_empty());
+        assert_eq!(plan.to_string(), "No changes\n");
+    }
+
+    #[test]
+    fn should_plan_changes() {
+        let mut legacy = get_product();
+
+        legacy["id"] = json!(Uuid::new_v4());
+        legacy["name"] = json!("Legacy plan");
+
+        let live: Vec<Product> = serde_json::from_value(json!([get_product(), legacy])).unwrap();
+        let mut pro = get_desired();
+        let mut team = get_desired();
+
+        pro.price = DesiredPrice::Fixed {
+            amount: 1500,
+            currency: "usd".to_owned(),
+        };
+        pro.benefits = vec![Uuid::new_v4()];
+        team.name = "Team plan".to_owned();
+
+        let catalog = Catalog {
+            archive_unlisted: true,
+            products: vec![pro, team],
+            ..Default::default()
+        };
+
+        let plan = diff(&catalog, &live);
+
+        assert_eq!(
+            plan.to_string(),
+            "~ update \"Pro plan\" (price, benefits)\n+ create \"Team plan\"\n- archive \"Legacy plan\"\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn should_plan_from_every_page() {
+        let mock = MockServer::start().await;
+        let mut legacy = get_product();
+
+        legacy["id"] = json!(Uuid::new_v4());
+        legacy["name"] = json!("Legacy plan");
+
+        for (page, product) in [(None, get_product()), (Some("2"), legacy)] {
+            let list = json!({
+                "items": [product],
+                "pagination": { "total_count": 2, "max_page": 2 },
+            });
+            let request = Mock::given(matchers::method("GET")).and(matchers::path("/products"));
+            let request = match page {
+                Some(page) => request.and(matchers::query_param("page", page)),
+                None => request.and(matchers::query_param_is_missing("page")),
+            };
+
+            request
+                .respond_with(ResponseTemplate::new(200).set_body_json(list))
+                .expect(1)
+                .mount(&mock)
+                .await;
+        }
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+        let catalog = Catalog {
+            archive_unlisted: true,
+            products: vec![get_desired()],
+            ..Default::default()
+        };
+
+        let result = plan(&polar, &catalog).await;
+
+        assert!(result.is_ok_and(|plan| plan.to_string() == "- archive \"Legacy plan\"\n"));
+    }
+
+    #[tokio::test]
+    async fn should_apply_plan() {
+        let mock = MockServer::start().await;
+        let product = get_product();
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/products"))
+            .and(matchers::body_partial_json(json!({
+                "name": "Pro plan",
+                "prices": [{ "amount_type": "fixed", "price_amount": 1000 }],
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(&product))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path(format!(
+                "/products/{}/benefits",
+                product["id"].as_str().unwrap()
+            )))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&product))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+        let mut desired = get_desired();
+
+        desired.benefits = vec![Uuid::new_v4()];
+
+        let result = apply(
+            &polar,
+            &Plan {
+                organization_id: None,
+                changes: vec![Change::Create(desired)],
+            },
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_keep_metered_prices_when_updating_price() {
+        let mock = MockServer::start().await;
+        let mut product = get_product();
+        let mut metered = product["prices"][0].clone();
+        let metered_id = Uuid::new_v4();
+        let static_id = product["prices"][0]["id"].clone();
+
+        metered["id"] = json!(metered_id);
+        metered["amount_type"] = json!("metered_unit");
+        metered["price_amount"] = json!(null);
+        metered["unit_amount"] = json!("0.5");
+        metered["meter_id"] = json!(Uuid::new_v4());
+        product["prices"] = json!([metered, product["prices"][0]]);
+
+        let path = format!("/products/{}", product["id"].as_str().unwrap());
+
+        Mock::given(matchers::method("GET"))
+            .and(matchers::path(&path))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&product))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("PATCH"))
+            .and(matchers::path(&path))
+            .and(move |request: &wiremock::Request| {
+                let body: Value = request.body_json().unwrap();
+                let prices = body["prices"].as_array().unwrap();
+
+                prices.len() == 2
+                    && prices[0]["id"] == json!(metered_id)
+                    && prices[1]["id"] != static_id
+                    && prices[1]["price_amount"] == json!(1500)
+            })
+            .respond_with(ResponseTemplate::new(200).set_body_json(&product))
+            .expect(1)
+            .mount(&mock)
+            .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+        let mut desired = get_desired();
+
+        desired.price = DesiredPrice::Fixed {
+            amount: 1500,
+            currency: "usd".to_owned(),
+        };
+
+        let result = apply(
+            &polar,
+            &Plan {
+                organization_id: None,
+                changes: vec![Change::Update {
+                    id: serde_json::from_value(product["id"].clone()).unwrap(),
+                    product: desired,
+                    fields: vec!["price"],
+                }],
+            },
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn should_load_catalog_from_toml() {
+        let catalog = Catalog::from_toml_str(
+            r#"
+            archive_unlisted = true
+
+            [[products]]
+            name = "Pro plan"
+            recurring_interval = "month"
+            price = { amount_type = "fixed", amount = 1000, currency = "USD" }
+
+            [[products]]
+            name = "Tip jar"
+            price = { amount_type = "custom", currency = "usd", minimum_amount = 100 }
+            metadata = { tier = "tip" }
+            "#,
+        )
+        .unwrap();
+        let mut tip_jar = get_desired();
+
+        tip_jar.name = "Tip jar".to_owned();
+        tip_jar.recurring_interval = None;
+        tip_jar.price = DesiredPrice::Custom {
+            currency: "usd".to_owned(),
+            minimum_amount: Some(100),
+            maximum_amount: None,
+            preset_amount: None,
+        };
+        tip_jar.metadata = HashMap::from([("tier".to_owned(), "tip".to_owned())]);
+
+        assert_eq!(
+            catalog,
+            Catalog {
+                organization_id: None,
+                archive_unlisted: true,
+                products: vec![get_desired(), tip_jar],
+            }
+        );
+    }
+}
@@ -0,0 +1,11 @@
+use std::fs::File;
+use std::io::BufReader;
+
+use serde::de::DeserializeOwned;
+
+/// Reads `fixtures/{name}.json`.
+pub(crate) fn get_fixture<T: DeserializeOwned>(name: &str) -> T {
+    let file = File::open(format!("fixtures/{name}.json")).unwrap();
+
+    serde_json::from_reader(BufReader::new(file)).unwrap()
+}
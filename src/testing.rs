@@ -0,0 +1,247 @@
+//! Helpers to test an app against Polar without the sandbox. Requires the `testing` feature.
+//!
+//! ```
+//! use polar_rs::testing::SubscriptionSimulator;
+//! use polar_rs::{Subscription, WebhookEventType};
+//!
+//! # fn run(product: serde_json::Value) -> polar_rs::PolarResult<()> {
+//! for event in SubscriptionSimulator::new(product)?.with_cycles(2).events() {
+//!     if event.r#type == WebhookEventType::SubscriptionRevoked {
+//!         let subscription: Subscription = event.data_as()?;
+//!
+//!         println!("Revoke access of {}", subscription.customer_id);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{DateTime, Months, TimeDelta, Utc};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{AmountType, PolarError, PolarResult, Product, RecurringInterval, WebhookEvent, WebhookEventType};
+
+/// Simulates the webhook events Polar sends during the lifecycle of a subscription: it's created, becomes active,
+/// renews for a number of cycles, is canceled at the end of the last one and is finally revoked.
+///
+/// The data of every event is a subscription as sent by Polar, so it can be decoded with
+/// [`WebhookEvent::data_as`]. Orders and benefit grants are not simulated.
+pub struct SubscriptionSimulator {
+    product: Value,
+    customer: Value,
+    amount: i64,
+    currency: String,
+    recurring_interval: RecurringInterval,
+    started_at: DateTime<Utc>,
+    cycles: usize,
+}
+
+impl SubscriptionSimulator {
+    /// Creates a simulator for a recurring product, given as returned by the API, e.g. read from a fixture.
+    ///
+    /// The subscription is billed with the first fixed or custom price of the product, and renews once by default.
+    pub fn new(product: Value) -> PolarResult<Self> {
+        let parsed: Product = serde_json::from_value(product.clone())?;
+        let recurring_interval = parsed
+            .recurring_interval
+            .ok_or_else(|| PolarError::Request("product is not recurring".to_owned()))?;
+        let price = parsed
+            .prices
+            .iter()
+            .find(|price| !price.is_archived && !matches!(price.amount_type, AmountType::MeteredUnit));
+        let started_at = Utc::now();
+
+        Ok(Self {
+            customer: json!({
+                "id": Uuid::new_v4(),
+                "created_at": started_at,
+                "modified_at": null,
+                "metadata": {},
+                "external_id": null,
+                "email": "customer@example.com",
+                "email_verified": true,
+                "name": null,
+                "billing_address": null,
+                "tax_id": null,
+                "organization_id": parsed.organization_id,
+                "deleted_at": null,
+                "avatar_url": "https://www.gravatar.com/avatar",
+            }),
+            amount: price
+                .and_then(|price| price.price_amount.or(price.preset_amount))
+                .unwrap_or_default(),
+            currency: price
+                .and_then(|price| price.price_currency.clone())
+                .unwrap_or_else(|| "usd".to_owned()),
+            recurring_interval,
+            started_at,
+            cycles: 1,
+            product,
+        })
+    }
+
+    /// Sets the customer subscribing, given as returned by the API. A customer is made up by default.
+    pub fn with_customer(mut self, customer: Value) -> Self {
+        self.customer = customer;
+        self
+    }
+
+    /// Sets when the subscription starts, defaults to now.
+    pub fn starting_at(mut self, started_at: DateTime<Utc>) -> Self {
+        self.started_at = started_at;
+        self
+    }
+
+    /// Sets how many times the subscription renews before being canceled.
+    pub fn with_cycles(mut self, cycles: usize) -> Self {
+        self.cycles = cycles;
+        self
+    }
+
+    /// Returns the events in the order Polar would send them.
+    pub fn events(&self) -> Vec<WebhookEvent> {
+        let mut period_start = self.started_at;
+        let mut period_end = self.next_period(period_start);
+        let mut subscription = json!({
+            "created_at": self.started_at,
+            "modified_at": null,
+            "id": Uuid::new_v4(),
+            "amount": self.amount,
+            "currency": self.currency,
+            "recurring_interval": self.recurring_interval,
+            "status": "incomplete",
+            "current_period_start": period_start,
+            "current_period_end": period_end,
+            "cancel_at_period_end": false,
+            "canceled_at": null,
+            "started_at": null,
+            "ends_at": null,
+            "ended_at": null,
+            "customer_id": self.customer["id"],
+            "product_id": self.product["id"],
+            "discount_id": null,
+            "checkout_id": null,
+            "customer_cancellation_reason": null,
+            "customer_cancellation_comment": null,
+            "metadata": {},
+            "customer": self.customer,
+            "product": self.product,
+            "discount": null,
+            "prices": self.product["prices"],
+            "meters": [],
+            "custom_field_data": {},
+        });
+        let mut events = vec![event(
+            WebhookEventType::SubscriptionCreated,
+            period_start,
+            &subscription,
+        )];
+
+        subscription["status"] = json!("active");
+        subscription["started_at"] = json!(period_start);
+        subscription["modified_at"] = json!(period_start);
+        events.push(event(WebhookEventType::SubscriptionActive, period_start, &subscription));
+
+        for _ in 0..self.cycles {
+            period_start = period_end;
+            period_end = self.next_period(period_start);
+
+            subscription["current_period_start"] = json!(period_start);
+            subscription["current_period_end"] = json!(period_end);
+            subscription["modified_at"] = json!(period_start);
+            events.push(event(
+                WebhookEventType::SubscriptionUpdated,
+                period_start,
+                &subscription,
+            ));
+        }
+
+        let canceled_at = period_start + (period_end - period_start) / 2;
+
+        subscription["cancel_at_period_end"] = json!(true);
+        subscription["canceled_at"] = json!(canceled_at);
+        subscription["ends_at"] = json!(period_end);
+        subscription["modified_at"] = json!(canceled_at);
+        events.push(event(
+            WebhookEventType::SubscriptionCanceled,
+            canceled_at,
+            &subscription,
+        ));
+
+        subscription["status"] = json!("canceled");
+        subscription["ended_at"] = json!(period_end);
+        subscription["modified_at"] = json!(period_end);
+        events.push(event(WebhookEventType::SubscriptionRevoked, period_end, &subscription));
+
+        events
+    }
+
+    fn next_period(&self, start: DateTime<Utc>) -> DateTime<Utc> {
+        let end = match self.recurring_interval {
+            RecurringInterval::Day => start.checked_add_signed(TimeDelta::days(1)),
+            RecurringInterval::Week => start.checked_add_signed(TimeDelta::weeks(1)),
+            RecurringInterval::Month => start.checked_add_months(Months::new(1)),
+            RecurringInterval::Year => start.checked_add_months(Months::new(12)),
+        };
+
+        end.unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+}
+
+fn event(r#type: WebhookEventType, timestamp: DateTime<Utc>, data: &Value) -> WebhookEvent {
+    WebhookEvent {
+        r#type,
+        timestamp,
+        data: data.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::get_fixture;
+    use crate::{Subscription, SubscriptionStatus};
+
+    fn get_product() -> Value {
+        get_fixture("product")
+    }
+
+    #[test]
+    fn should_simulate_subscription_lifecycle() {
+        let started_at = DateTime::from_timestamp(1_735_689_600, 0).unwrap();
+
+        let events = SubscriptionSimulator::new(get_product())
+            .unwrap()
+            .starting_at(started_at)
+            .with_cycles(2)
+            .events();
+
+        assert_eq!(
+            events.iter().map(|event| event.r#type).collect::<Vec<_>>(),
+            [
+                WebhookEventType::SubscriptionCreated,
+                WebhookEventType::SubscriptionActive,
+                WebhookEventType::SubscriptionUpdated,
+                WebhookEventType::SubscriptionUpdated,
+                WebhookEventType::SubscriptionCanceled,
+                WebhookEventType::SubscriptionRevoked,
+            ]
+        );
+
+        let revoked: Subscription = events.last().unwrap().data_as().unwrap();
+
+        assert!(matches!(revoked.status, SubscriptionStatus::Canceled));
+        assert_eq!(revoked.started_at, Some(started_at));
+        assert_eq!(revoked.ended_at, DateTime::from_timestamp(1_743_465_600, 0));
+    }
+
+    #[test]
+    fn should_not_simulate_one_time_product() {
+        let mut product = get_product();
+
+        product["recurring_interval"] = json!(null);
+
+        assert!(SubscriptionSimulator::new(product).is_err());
+    }
+}
@@ -0,0 +1,11 @@
+//! Types from dependencies used in the public API, re-exported so they always match the versions used by this crate.
+//!
+//! ```
+//! use polar_rs::types::{DateTime, RedirectPolicy, Url, Utc, Uuid};
+//! ```
+
+pub use chrono::{DateTime, Utc};
+#[cfg(not(target_arch = "wasm32"))]
+pub use reqwest::redirect::Policy as RedirectPolicy;
+pub use url::Url;
+pub use uuid::Uuid;
@@ -0,0 +1,520 @@
+//! Uploads of files, e.g. product media or downloadables, covering the whole handshake: the file is created, its
+//! parts are uploaded to their presigned URLs, then the upload is completed.
+//!
+//! ```no_run
+//! use polar_rs::upload::FileUploader;
+//! use polar_rs::{FileServiceType, Polar};
+//!
+//! # async fn run(polar: &Polar) -> polar_rs::PolarResult<()> {
+//! let file = FileUploader::new(polar)
+//!     .on_progress(|progress| println!("{}/{} bytes", progress.uploaded, progress.total))
+//!     .upload_path("cover.png", "image/png", FileServiceType::ProductMedia)
+//!     .await?;
+//!
+//! println!("Uploaded {}", file.id);
+//! # Ok(())
+//! # }
+//! ```
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use bytes::Bytes;
+use reqwest::header::ETAG;
+use sha2::{Digest, Sha256};
+#[cfg(not(target_arch = "wasm32"))]
+use tokio::io::{AsyncRead, AsyncReadExt};
+use uuid::Uuid;
+
+use crate::{
+    FileCreateParams, FileRead, FileServiceType, FileUpload, FileUploadCompletedParams, FileUploadCompletedPart,
+    FileUploadParams, FileUploadPart, FileUploadPartParams, Polar, PolarError, PolarResult,
+};
+
+/// Default size of the parts.
+const DEFAULT_PART_SIZE: usize = 10 * 1024 * 1024;
+
+/// Minimum size of the parts, S3 requires at least 5 MiB for every part but the last one.
+const MIN_PART_SIZE: usize = 5 * 1024 * 1024;
+
+/// Default number of times the upload of a part is retried.
+const DEFAULT_MAX_RETRIES: usize = 3;
+
+/// How much of a file has been uploaded, reported after each part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UploadProgress {
+    /// Number of bytes uploaded so far.
+    pub uploaded: u64,
+    /// Size of the file, in bytes.
+    pub total: u64,
+}
+
+type ProgressHook<'a> = Box<dyn Fn(UploadProgress) + Send + Sync + 'a>;
+
+/// Uploads files in parts, each one with its SHA-256 checksum and retried on failure.
+pub struct FileUploader<'a> {
+    polar: &'a Polar,
+    organization_id: Option<Uuid>,
+    part_size: usize,
+    max_retries: usize,
+    progress_hook: Option<ProgressHook<'a>>,
+}
+
+impl<'a> FileUploader<'a> {
+    pub fn new(polar: &'a Polar) -> Self {
+        Self {
+            polar,
+            organization_id: None,
+            part_size: DEFAULT_PART_SIZE,
+            max_retries: DEFAULT_MAX_RETRIES,
+            progress_hook: None,
+        }
+    }
+
+    /// Sets the organization owning the files. **Required unless you use an organization token.**
+    pub fn with_organization_id(mut self, organization_id: Uuid) -> Self {
+        self.organization_id = Some(organization_id);
+        self
+    }
+
+    /// Sets the size of the parts, in bytes, defaults to 10 MiB. Sizes under 5 MiB, the minimum accepted by the
+    /// storage, are raised to it.
+    pub fn with_part_size(mut self, part_size: usize) -> Self {
+        self.part_size = part_size.max(MIN_PART_SIZE);
+        self
+    }
+
+    /// Sets how many times the upload of a part is retried when it fails, defaults to 3.
+    pub fn with_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets a hook called after the upload of each part.
+    pub fn on_progress<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(UploadProgress) + Send + Sync + 'a,
+    {
+        self.progress_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Uploads a local file, named after the last component of its path, reading it one part at a time. Not
+    /// available on WebAssembly, use [`FileUploader::upload`] there.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn upload_path<P: AsRef<Path>>(
+        &self,
+        path: P,
+        mime_type: &str,
+        service: FileServiceType,
+    ) -> PolarResult<FileRead> {
+        let path = path.as_ref();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| PolarError::Request(format!("{} is not a file name", path.display())))?;
+        let file = tokio::fs::File::open(path).await?;
+        let size = file.metadata().await?.len();
+
+        self.upload_reader(name, mime_type, service, size, file).await
+    }
+
+    /// Uploads a file of `size` bytes read from `reader`, one part at a time so only one part is held in memory.
+    ///
+    /// The checksum of a part is computed while it's read, so unlike with [`FileUploader::upload`], checksums are only
+    /// sent when completing the upload. Not available on WebAssembly.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn upload_reader<R: AsyncRead + Unpin>(
+        &self,
+        name: &str,
+        mime_type: &str,
+        service: FileServiceType,
+        size: u64,
+        mut reader: R,
+    ) -> PolarResult<FileRead> {
+        let file = self
+            .create(name, mime_type, service, size, None, self.split(size))
+            .await?;
+        let mut read = 0;
+        let mut uploaded = UploadProgress {
+            uploaded: 0,
+            total: file.size,
+        };
+        let mut parts = Vec::with_capacity(file.upload.parts.len());
+
+        for part in &file.upload.parts {
+            // Parts are read in order, so they must follow each other.
+            let chunk_size = part
+                .chunk_end
+                .checked_sub(part.chunk_start)
+                .filter(|_| part.chunk_start == read && part.chunk_end < size)
+                .and_then(|size| usize::try_from(size + 1).ok())
+                .ok_or_else(|| PolarError::Request(format!("part {} is out of the file bounds", part.number)))?;
+            let mut chunk = vec![0; chunk_size];
+
+            reader.read_exact(&mut chunk).await?;
+            read += chunk_size as u64;
+
+            let checksum = checksum(&chunk);
+
+            parts.push(
+                self.send_part(part, chunk.into(), Some(checksum), &mut uploaded)
+                    .await?,
+            );
+        }
+
+        self.complete(file, parts).await
+    }
+
+    /// Uploads a file from memory.
+    pub async fn upload(
+        &self,
+        name: &str,
+        mime_type: &str,
+        service: FileServiceType,
+        data: &[u8],
+    ) -> PolarResult<FileRead> {
+        let mut part_params = self.split(data.len() as u64);
+
+        for part in &mut part_params {
+            let chunk = &data[part.chunk_start as usize..=part.chunk_end as usize];
+
+            part.checksum_sha256_base64 = Some(checksum(chunk));
+        }
+
+        let file = self
+            .create(
+                name,
+                mime_type,
+                service,
+                data.len() as u64,
+                Some(checksum(data)),
+                part_params,
+            )
+            .await?;
+        let mut uploaded = UploadProgress {
+            uploaded: 0,
+            total: file.size,
+        };
+        let mut parts = Vec::with_capacity(file.upload.parts.len());
+
+        for part in &file.upload.parts {
+            let chunk = usize::try_from(part.chunk_start)
+                .ok()
+                .zip(usize::try_from(part.chunk_end).ok())
+                .and_then(|(start, end)| data.get(start..=end))
+                .ok_or_else(|| PolarError::Request(format!("part {} is out of the file bounds", part.number)))?;
+            let checksum = part.checksum_sha256_base64.clone();
+
+            parts.push(
+                self.send_part(part, Bytes::copy_from_slice(chunk), checksum, &mut uploaded)
+                    .await?,
+            );
+        }
+
+        self.complete(file, parts).await
+    }
+
+    async fn create(
+        &self,
+        name: &str,
+        mime_type: &str,
+        service: FileServiceType,
+        size: u64,
+        checksum: Option<String>,
+        parts: Vec<FileUploadPartParams>,
+    ) -> PolarResult<FileUpload> {
+        if size == 0 {
+            return Err(PolarError::Request("file is empty".to_owned()));
+        }
+
+        self.polar
+            .create_file(&FileCreateParams {
+                organization_id: self.organization_id,
+                name: name.to_owned(),
+                mime_type: mime_type.to_owned(),
+                size,
+                checksum_sha256_base64: checksum,
+                upload: FileUploadParams { parts },
+                service,
+                version: None,
+            })
+            .await
+    }
+
+    async fn complete(&self, file: FileUpload, parts: Vec<FileUploadCompletedPart>) -> PolarResult<FileRead> {
+        self.polar
+            .complete_file_upload(
+                file.id,
+                &FileUploadCompletedParams {
+                    id: file.upload.id,
+                    path: file.upload.path,
+                    parts,
+                },
+            )
+            .await
+    }
+
+    /// Splits a file of `size` bytes into parts, without their checksums.
+    fn split(&self, size: u64) -> Vec<FileUploadPartParams> {
+        let part_size = self.part_size as u64;
+
+        (0..size.div_ceil(part_size))
+            .map(|index| {
+                let chunk_start = index * part_size;
+
+                FileUploadPartParams {
+                    number: index as usize + 1,
+                    chunk_start,
+                    chunk_end: (chunk_start + part_size).min(size) - 1,
+                    checksum_sha256_base64: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Uploads a part and reports the progress, returning the part to complete the upload with.
+    async fn send_part(
+        &self,
+        part: &FileUploadPart,
+        chunk: Bytes,
+        checksum: Option<String>,
+        progress: &mut UploadProgress,
+    ) -> PolarResult<FileUploadCompletedPart> {
+        let size = chunk.len() as u64;
+        let checksum_etag = self.upload_part(part, chunk).await?;
+
+        progress.uploaded += size;
+
+        if let Some(hook) = &self.progress_hook {
+            hook(*progress);
+        }
+
+        Ok(FileUploadCompletedPart {
+            number: part.number,
+            checksum_etag,
+            checksum_sha256_base64: checksum,
+        })
+    }
+
+    /// Uploads a part to its presigned URL, returning its `ETag`.
+    async fn upload_part(&self, part: &FileUploadPart, chunk: Bytes) -> PolarResult<String> {
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+
+            // The URL is signed, the access token must not be sent to the storage. The chunk is shared between
+            // attempts, not copied.
+            let mut request = self.polar.client.put(part.url.clone()).body(chunk.clone());
+
+            for (name, value) in &part.headers {
+                request = request.header(name, value);
+            }
+
+            let error = match request.send().await {
+                Ok(response) if response.status().is_success() => {
+                    return response
+                        .headers()
+                        .get(ETAG)
+                        .and_then(|etag| etag.to_str().ok())
+                        .map(str::to_owned)
+                        .ok_or_else(|| PolarError::Request(format!("part {} has no ETag", part.number)));
+                }
+                Ok(response) if response.status().is_server_error() => PolarError::Unknown(response.text().await?),
+                Ok(response) => return Err(PolarError::Unknown(response.text().await?)),
+                Err(err) => err.into(),
+            };
+
+            if attempts > self.max_retries {
+                return Err(error);
+            }
+        }
+    }
+}
+
+fn checksum(data: &[u8]) -> String {
+    BASE64.encode(Sha256::digest(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use serde_json::{Value, json};
+    use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
+
+    use super::*;
+    use crate::test_support::get_fixture;
+
+    async fn mount_upload(mock: &MockServer) -> Value {
+        let mut file_upload = get_fixture::<Value>("file_upload");
+
+        file_upload["upload"]["parts"][0]["url"] = json!(format!("{}/upload/1", mock.uri()));
+        file_upload["upload"]["parts"][0]["headers"] = json!({ "x-amz-checksum-sha256": "<string>" });
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/files"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(&file_upload))
+            .expect(1)
+            .mount(mock)
+            .await;
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path(format!(
+                "/files/{}/uploaded",
+                file_upload["id"].as_str().unwrap()
+            )))
+            .and(matchers::body_partial_json(json!({
+                "parts": [{ "number": 1, "checksum_etag": "\"etag-1\"" }],
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(get_fixture::<Value>("file")))
+            .expect(1)
+            .mount(mock)
+            .await;
+
+        file_upload
+    }
+
+    #[tokio::test]
+    async fn should_upload_file() {
+        let mock = MockServer::start().await;
+
+        mount_upload(&mock).await;
+
+        Mock::given(matchers::method("PUT"))
+            .and(matchers::path("/upload/1"))
+            .and(matchers::header("x-amz-checksum-sha256", "<string>"))
+            .and(|request: &wiremock::Request| !request.headers.contains_key("authorization"))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("PUT"))
+            .and(matchers::path("/upload/1"))
+            .and(|request: &wiremock::Request| !request.headers.contains_key("authorization"))
+            .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"etag-1\""))
+            .mount(&mock)
+            .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+        let progress = Arc::new(Mutex::new(Vec::new()));
+
+        let result = FileUploader::new(&polar)
+            .on_progress({
+                let progress = progress.clone();
+
+                move |update| progress.lock().unwrap().push(update)
+            })
+            .upload("media.png", "image/png", FileServiceType::ProductMedia, &[0; 123])
+            .await;
+
+        assert!(result.is_ok_and(|file| file.is_uploaded));
+        assert_eq!(
+            *progress.lock().unwrap(),
+            [UploadProgress {
+                uploaded: 123,
+                total: 123
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_not_upload_file_when_part_keeps_failing() {
+        let mock = MockServer::start().await;
+        let mut file_upload = get_fixture::<Value>("file_upload");
+
+        file_upload["upload"]["parts"][0]["url"] = json!(format!("{}/upload/1", mock.uri()));
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/files"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(&file_upload))
+            .mount(&mock)
+            .await;
+
+        Mock::given(matchers::method("PUT"))
+            .and(matchers::path("/upload/1"))
+            .respond_with(ResponseTemplate::new(503))
+            .expect(2)
+            .mount(&mock)
+            .await;
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let result = FileUploader::new(&polar)
+            .with_max_retries(1)
+            .upload("media.png", "image/png", FileServiceType::ProductMedia, &[0; 123])
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_upload_file_from_path() {
+        let mock = MockServer::start().await;
+        let path = std::env::temp_dir().join(format!("{}.png", Uuid::new_v4()));
+
+        mount_upload(&mock).await;
+
+        Mock::given(matchers::method("PUT"))
+            .and(matchers::path("/upload/1"))
+            .and(matchers::body_bytes(vec![1; 123]))
+            .respond_with(ResponseTemplate::new(200).insert_header("etag", "\"etag-1\""))
+            .mount(&mock)
+            .await;
+
+        std::fs::write(&path, [1; 123]).unwrap();
+
+        let polar = Polar::new(mock.uri(), "<TOKEN>").unwrap();
+
+        let result = FileUploader::new(&polar)
+            .upload_path(&path, "image/png", FileServiceType::ProductMedia)
+            .await;
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_ok_and(|file| file.is_uploaded));
+
+        let requests = mock.received_requests().await.unwrap();
+        let completed: Value = requests.last().unwrap().body_json().unwrap();
+
+        assert_eq!(
+            completed["parts"][0]["checksum_sha256_base64"],
+            json!(checksum(&[1; 123]))
+        );
+    }
+
+    #[test]
+    fn should_split_file_into_parts() {
+        let polar = Polar::new("https://sandbox-api.polar.sh/v1/", "<TOKEN>").unwrap();
+
+        let parts = FileUploader::new(&polar)
+            .with_part_size(MIN_PART_SIZE)
+            .split(2 * MIN_PART_SIZE as u64 + 10);
+
+        assert_eq!(
+            parts
+                .iter()
+                .map(|part| (part.number, part.chunk_start, part.chunk_end))
+                .collect::<Vec<_>>(),
+            [
+                (1, 0, MIN_PART_SIZE as u64 - 1),
+                (2, MIN_PART_SIZE as u64, 2 * MIN_PART_SIZE as u64 - 1),
+                (3, 2 * MIN_PART_SIZE as u64, 2 * MIN_PART_SIZE as u64 + 9)
+            ]
+        );
+    }
+
+    #[test]
+    fn should_raise_part_size_to_minimum() {
+        let polar = Polar::new("https://sandbox-api.polar.sh/v1/", "<TOKEN>").unwrap();
+
+        let parts = FileUploader::new(&polar).with_part_size(10).split(25);
+
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].chunk_end, 24);
+    }
+}
@@ -0,0 +1,213 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::{DateTime, TimeDelta, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use sha2::Sha256;
+
+use crate::{PolarError, PolarResult, WebhookEventType};
+
+/// Maximum difference between the timestamp of a webhook and the current time, to prevent replay attacks.
+const WEBHOOK_TOLERANCE: TimeDelta = TimeDelta::minutes(5);
+
+/// Headers of a webhook request, following the Standard Webhooks specification.
+pub struct WebhookHeaders<'a> {
+    /// Value of the `webhook-id` header.
+    pub id: &'a str,
+    /// Value of the `webhook-timestamp` header, in seconds since the Unix epoch.
+    pub timestamp: &'a str,
+    /// Value of the `webhook-signature` header.
+    pub signature: &'a str,
+}
+
+impl<'a> WebhookHeaders<'a> {
+    /// Reads the webhook headers from a header map, e.g. the one of an `axum` or `actix-web` request.
+    ///
+    /// Returns `None` if a header is missing or isn't valid UTF-8.
+    pub fn from_header_map(headers: &'a HeaderMap) -> Option<Self> {
+        let get = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+
+        Some(Self {
+            id: get("webhook-id")?,
+            timestamp: get("webhook-timestamp")?,
+            signature: get("webhook-signature")?,
+        })
+    }
+}
+
+/// An event sent by Polar to a webhook endpoint, as returned by [`verify_webhook`].
+#[derive(Debug, Deserialize)]
+pub struct WebhookEvent {
+    /// The type of the event.
+    pub r#type: WebhookEventType,
+    /// Timestamp of the event.
+    pub timestamp: DateTime<Utc>,
+    /// The object the event is about, e.g. an order for `order.paid`.
+    pub data: Value,
+}
+
+impl WebhookEvent {
+    /// Decodes the object the event is about, e.g. as an [`Order`](crate::Order) for `order.paid`.
+    pub fn data_as<T: DeserializeOwned>(&self) -> PolarResult<T> {
+        Ok(serde_json::from_value(self.data.clone())?)
+    }
+}
+
+/// Verifies the signature of a webhook request and decodes its body.
+///
+/// `secret` is the secret of the webhook endpoint, as shown in the Polar dashboard. Requests signed with another
+/// secret, or sent more than 5 minutes ago, are rejected with [`PolarError::Unauthorized`].
+pub fn verify_webhook(secret: &str, headers: &WebhookHeaders<'_>, body: &[u8]) -> PolarResult<WebhookEvent> {
+    verify_webhook_at(secret, headers, body, Utc::now())
+}
+
+/// Same as [`verify_webhook`], but checks the timestamp of the request against `now` instead of the system clock,
+/// e.g. on edge runtimes where the clock is provided by the request context.
+pub fn verify_webhook_at(
+    secret: &str,
+    headers: &WebhookHeaders<'_>,
+    body: &[u8],
+    now: DateTime<Utc>,
+) -> PolarResult<WebhookEvent> {
+    let timestamp = headers
+        .timestamp
+        .parse()
+        .ok()
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+        .ok_or(PolarError::Unauthorized)?;
+
+    if (now - timestamp).abs() > WEBHOOK_TOLERANCE {
+        return Err(PolarError::Unauthorized);
+    }
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).map_err(|_| PolarError::Unauthorized)?;
+
+    mac.update(headers.id.as_bytes());
+    mac.update(b".");
+    mac.update(headers.timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+
+    // Several signatures are sent while the secret is being rotated, any of them is enough.
+    let is_valid = headers
+        .signature
+        .split_whitespace()
+        .filter_map(|signature| signature.strip_prefix("v1,"))
+        .filter_map(|signature| BASE64.decode(signature).ok())
+        .any(|signature| mac.clone().verify_slice(&signature).is_ok());
+
+    if !is_valid {
+        return Err(PolarError::Unauthorized);
+    }
+
+    Ok(serde_json::from_slice(body)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "polar_whs_secret";
+    const BODY: &[u8] = br#"{"type": "order.paid", "timestamp": "2025-01-01T00:00:00Z", "data": {}}"#;
+
+    fn sign(secret: &str, id: &str, timestamp: &str, body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+
+        mac.update(format!("{id}.{timestamp}.").as_bytes());
+        mac.update(body);
+
+        format!("v1,{}", BASE64.encode(mac.finalize().into_bytes()))
+    }
+
+    fn now() -> DateTime<Utc> {
+        DateTime::from_timestamp(1_735_689_600, 0).unwrap()
+    }
+
+    #[test]
+    fn should_verify_webhook() {
+        let signature = sign(SECRET, "msg_1", "1735689600", BODY);
+        let headers = WebhookHeaders {
+            id: "msg_1",
+            timestamp: "1735689600",
+            signature: &format!("v1,invalid {signature}"),
+        };
+
+        let result = verify_webhook_at(SECRET, &headers, BODY, now());
+
+        assert!(result.is_ok_and(|event| event.r#type == WebhookEventType::OrderPaid));
+    }
+
+    #[test]
+    fn should_verify_webhook_of_unknown_type() {
+        let body = br#"{"type": "payout.created", "timestamp": "2025-01-01T00:00:00Z", "data": {}}"#;
+        let signature = sign(SECRET, "msg_1", "1735689600", body);
+        let headers = WebhookHeaders {
+            id: "msg_1",
+            timestamp: "1735689600",
+            signature: &signature,
+        };
+
+        let result = verify_webhook_at(SECRET, &headers, body, now());
+
+        assert!(result.is_ok_and(|event| event.r#type == WebhookEventType::Unknown));
+    }
+
+    #[test]
+    fn should_not_verify_webhook_with_other_secret() {
+        let signature = sign("polar_whs_other", "msg_1", "1735689600", BODY);
+        let headers = WebhookHeaders {
+            id: "msg_1",
+            timestamp: "1735689600",
+            signature: &signature,
+        };
+
+        let result = verify_webhook_at(SECRET, &headers, BODY, now());
+
+        assert!(matches!(result, Err(PolarError::Unauthorized)));
+    }
+
+    #[test]
+    fn should_not_verify_webhook_with_altered_body() {
+        let signature = sign(SECRET, "msg_1", "1735689600", BODY);
+        let headers = WebhookHeaders {
+            id: "msg_1",
+            timestamp: "1735689600",
+            signature: &signature,
+        };
+
+        let result = verify_webhook_at(SECRET, &headers, br#"{"type": "order.refunded"}"#, now());
+
+        assert!(matches!(result, Err(PolarError::Unauthorized)));
+    }
+
+    #[test]
+    fn should_not_verify_webhook_sent_too_long_ago() {
+        let signature = sign(SECRET, "msg_1", "1735689000", BODY);
+        let headers = WebhookHeaders {
+            id: "msg_1",
+            timestamp: "1735689000",
+            signature: &signature,
+        };
+
+        let result = verify_webhook_at(SECRET, &headers, BODY, now());
+
+        assert!(matches!(result, Err(PolarError::Unauthorized)));
+    }
+
+    #[test]
+    fn should_read_webhook_headers_from_header_map() {
+        let mut headers = HeaderMap::new();
+
+        headers.insert("webhook-id", "msg_1".parse().unwrap());
+        headers.insert("webhook-timestamp", "1735689600".parse().unwrap());
+
+        assert!(WebhookHeaders::from_header_map(&headers).is_none());
+
+        headers.insert("webhook-signature", "v1,abc".parse().unwrap());
+
+        assert!(WebhookHeaders::from_header_map(&headers).is_some_and(|headers| headers.id == "msg_1"));
+    }
+}